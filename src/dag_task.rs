@@ -0,0 +1,258 @@
+//! DagTask struct.
+//!
+//! This module defines the `DagTask` struct, which describes a parallel
+//! real-time task following the sporadic DAG model. Each job of the task is a
+//! Directed Acyclic Graph of subtasks, where an edge `(a, b)` states that
+//! subtask `b` can start only after subtask `a` has completed, while subtasks
+//! not ordered by the edges may execute in parallel on different cores.
+//!
+//! A DAG task is characterized by its **Volume** (total WCET), and by the
+//! length of its **Critical Path**, the longest chain of dependent subtasks,
+//! which bounds its response time from below even on infinitely many cores.
+
+use crate::prelude::*;
+use crate::utils::RTUtils;
+
+pub mod prelude {
+    pub use super::{
+        DagTask,
+    };
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct DagTask {
+    /// Worst Case Execution Times of the subtasks
+    pub subtasks: Vec<Time>,
+    /// Precedence constraints `(from, to)` between subtasks, by index
+    pub edges: Vec<(usize, usize)>,
+    /// Relative Deadline
+    pub deadline: Time,
+    /// (Minimum Inter-arrival) Period
+    pub period: Time,
+}
+
+impl DagTask {
+    pub fn new(subtasks: Vec<Time>, edges: Vec<(usize, usize)>, deadline: Time, period: Time) -> Self {
+        Self { subtasks, edges, deadline, period }
+    }
+
+    /// Sum of the subtasks WCETs, i.e. the volume of the DAG
+    pub fn total_wcet(&self) -> Time {
+        self.subtasks.iter().copied().sum()
+    }
+
+    /// Length of the longest path of the DAG, where the length of a path is
+    /// the sum of the WCETs of its subtasks.
+    ///
+    /// Panics if the edges contain a cycle or refer to nonexistent subtasks.
+    pub fn critical_path_length(&self) -> Time {
+        let n = self.subtasks.len();
+
+        let mut predecessors = vec![0usize; n];
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for &(from, to) in &self.edges {
+            successors[from].push(to);
+            predecessors[to] += 1;
+        }
+
+        // Longest path ending in each subtask, in topological order
+        let mut finish = self.subtasks.clone();
+        let mut ready: Vec<usize> = (0..n).filter(|&node| predecessors[node] == 0).collect();
+        let mut visited = 0;
+        while let Some(node) = ready.pop() {
+            visited += 1;
+            for &next in &successors[node] {
+                finish[next] = Time::max(finish[next], finish[node] + self.subtasks[next]);
+                predecessors[next] -= 1;
+                if predecessors[next] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+
+        assert!(visited == n, "The task graph contains a cycle");
+
+        finish.into_iter().max().unwrap_or_default()
+    }
+
+    /// Total WCET / Period
+    pub fn utilization(&self) -> f64 {
+        self.total_wcet() / self.period
+    }
+}
+
+impl RTUtils {
+    /// Number of cores needed to schedule the DAG tasks under federated
+    /// scheduling (Li et al., 2014), for constrained deadlines. Returns `None`
+    /// if a task cannot be scheduled on any number of cores.
+    ///
+    /// Each heavy task, whose total WCET exceeds its deadline, is assigned
+    /// `ceil((C - L) / (D - L))` dedicated cores, where `L` is its critical
+    /// path length, and is scheduled by any work-conserving scheduler. The
+    /// light tasks are executed sequentially, as `RTTask`s, and are assigned
+    /// by first-fit, in order of decreasing density, to the remaining cores,
+    /// which are scheduled by EDF.
+    pub fn federated_cores(dagtasks: &[DagTask]) -> Option<usize> {
+        let mut cores = 0;
+        let mut light: Vec<RTTask> = Vec::new();
+
+        for task in dagtasks {
+            let volume = task.total_wcet();
+            let critical_path = task.critical_path_length();
+
+            if volume > task.deadline {
+                if critical_path >= task.deadline {
+                    return None;
+                }
+
+                cores += f64::ceil((volume - critical_path) / (task.deadline - critical_path)) as usize;
+            } else {
+                light.push(RTTask {
+                    wcet: volume,
+                    deadline: task.deadline,
+                    period: task.period,
+                });
+            }
+        }
+
+        light.sort_by_key(|task| std::cmp::Reverse(ordered_float::OrderedFloat(task.density())));
+
+        let mut shared: Vec<Vec<RTTask>> = Vec::new();
+        for task in light {
+            let core = shared.iter_mut().find(|core| {
+                let mut candidate = core.to_vec();
+                candidate.push(task.clone());
+                Self::edf_qpa(&candidate)
+            });
+
+            match core {
+                Some(core) => core.push(task),
+                None => {
+                    if !Self::edf_qpa(std::slice::from_ref(&task)) {
+                        return None;
+                    }
+
+                    shared.push(vec![task]);
+                },
+            }
+        }
+
+        Some(cores + shared.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::Rng;
+    use crate::test_utils::*;
+
+    fn ns(value: f64) -> Time {
+        Time::nanos(value)
+    }
+
+    fn diamond() -> DagTask {
+        // 0 -> {1, 2} -> 3
+        DagTask::new(
+            vec![ns(1.0), ns(3.0), ns(2.0), ns(1.0)],
+            vec![(0, 1), (0, 2), (1, 3), (2, 3)],
+            ns(8.0),
+            ns(10.0),
+        )
+    }
+
+    #[test]
+    fn critical_path_and_utilization_of_a_diamond() {
+        let task = diamond();
+
+        assert_eq!(task.total_wcet(), ns(7.0));
+        assert_eq!(task.critical_path_length(), ns(5.0));
+        assert_eq!(task.utilization(), 0.7);
+    }
+
+    #[test]
+    fn critical_path_of_degenerate_graphs() {
+        // Independent subtasks
+        let parallel = DagTask::new(vec![ns(2.0), ns(4.0), ns(3.0)], vec![], ns(10.0), ns(10.0));
+        assert_eq!(parallel.critical_path_length(), ns(4.0));
+        assert_eq!(parallel.total_wcet(), ns(9.0));
+
+        // A chain, with the edges listed out of order
+        let chain = DagTask::new(vec![ns(2.0), ns(4.0), ns(3.0)], vec![(1, 2), (0, 1)], ns(10.0), ns(10.0));
+        assert_eq!(chain.critical_path_length(), ns(9.0));
+
+        let empty = DagTask::new(vec![], vec![], ns(10.0), ns(10.0));
+        assert_eq!(empty.critical_path_length(), Time::zero());
+        assert_eq!(empty.total_wcet(), Time::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "The task graph contains a cycle")]
+    fn critical_path_of_a_cycle() {
+        DagTask::new(vec![ns(1.0), ns(1.0)], vec![(0, 1), (1, 0)], ns(10.0), ns(10.0))
+            .critical_path_length();
+    }
+
+    #[test]
+    fn federated_cores_of_a_mixed_taskset() {
+        // Hand-computed example. The heavy task has C = 12 and L = 3, hence it
+        // gets ceil((12 - 3) / (8 - 3)) = 2 cores. The light tasks, by
+        // decreasing density, are 0.5 and 0.5, sharing a core at full
+        // utilization, and 0.2, which needs a core of its own.
+        let heavy = DagTask::new(vec![ns(3.0); 4], vec![], ns(8.0), ns(10.0));
+        let sequential = |wcet: f64, period: f64| DagTask::new(vec![ns(wcet)], vec![], ns(period), ns(period));
+        let light = [sequential(1.0, 5.0), sequential(2.0, 4.0), sequential(3.0, 6.0)];
+
+        assert_eq!(RTUtils::federated_cores(std::slice::from_ref(&heavy)), Some(2));
+        assert_eq!(RTUtils::federated_cores(&light), Some(2));
+        assert_eq!(RTUtils::federated_cores(&[heavy.clone(), light[0].clone(), light[1].clone(), light[2].clone()]), Some(4));
+        assert_eq!(RTUtils::federated_cores(&[]), Some(0));
+
+        // The diamond's volume fits its deadline, so it runs sequentially
+        assert_eq!(RTUtils::federated_cores(&[diamond()]), Some(1));
+    }
+
+    #[test]
+    fn federated_cores_of_infeasible_tasks() {
+        // The critical path does not fit the deadline
+        let long_path = DagTask::new(vec![ns(3.0), ns(3.0)], vec![(0, 1)], ns(5.0), ns(10.0));
+        assert_eq!(RTUtils::federated_cores(&[long_path]), None);
+
+        // ... not even with no slack left for the parallel part
+        let tight_path = DagTask::new(vec![ns(3.0), ns(2.0), ns(1.0)], vec![(0, 1)], ns(5.0), ns(10.0));
+        assert_eq!(RTUtils::federated_cores(&[diamond(), tight_path]), None);
+    }
+
+    #[test]
+    fn federated_cores_cover_the_utilization() {
+        let mut rng = Rng::new(182);
+        for _ in 0..1000 {
+            let dagtasks: Vec<DagTask> = (0..uniform(&mut rng, 1, 4))
+                .map(|_| {
+                    let n = uniform(&mut rng, 1, 6) as usize;
+                    let subtasks = (0..n).map(|_| ns(uniform(&mut rng, 1, 10) as f64)).collect();
+                    let edges = (0..n)
+                        .flat_map(|from| (from + 1..n).map(move |to| (from, to)))
+                        .filter(|_| uniform(&mut rng, 0, 2) == 0)
+                        .collect();
+                    let period = uniform(&mut rng, 5, 40) as f64;
+                    let deadline = uniform(&mut rng, 5, period as u64) as f64;
+                    DagTask::new(subtasks, edges, ns(deadline), ns(period))
+                })
+                .collect();
+
+            // Each heavy task gets at least its utilization in cores, and
+            // each shared core runs at most a utilization of one.
+            if let Some(cores) = RTUtils::federated_cores(&dagtasks) {
+                let utilization: f64 = dagtasks.iter().map(DagTask::utilization).sum();
+                assert!(cores as f64 >= utilization - 1e-9, "{dagtasks:?}");
+            }
+
+            // No number of cores helps a task whose critical path misses
+            if dagtasks.iter().any(|task| task.critical_path_length() > task.deadline) {
+                assert_eq!(RTUtils::federated_cores(&dagtasks), None, "{dagtasks:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! RTError enum.
+//!
+//! This module defines the `RTError` enum, returned by the fallible `try_*`
+//! variants of the analysis functions. Unlike their plain counterparts, these
+//! functions validate their inputs and never panic.
+
+pub mod prelude {
+    pub use super::{
+        RTError,
+    };
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RTError {
+    /// The taskset contains no tasks
+    EmptyTaskset,
+    /// The task at the given index has a non-finite, negative or zero
+    /// parameter where a positive one is required
+    InvalidTask(usize),
+    /// The result cannot be represented
+    Overflow,
+    /// An argument is out of its valid range
+    InvalidArgument(String),
+    /// A time which cannot be negative is negative or NaN
+    NegativeTime,
+    /// An iterative analysis did not converge within its iteration limit
+    IterationLimit,
+}
+
+impl std::fmt::Display for RTError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RTError::EmptyTaskset => write!(f, "Empty taskset"),
+            RTError::InvalidTask(index) => write!(f, "Invalid parameters for task {index}"),
+            RTError::Overflow => write!(f, "Overflow"),
+            RTError::InvalidArgument(reason) => write!(f, "Invalid argument: {reason}"),
+            RTError::NegativeTime => write!(f, "Negative time"),
+            RTError::IterationLimit => write!(f, "Iteration limit reached"),
+        }
+    }
+}
+
+impl std::error::Error for RTError { }
@@ -0,0 +1,246 @@
+//! Random taskset generation.
+//!
+//! This module provides the building blocks of schedulability experiments:
+//! tasksets are generated at a target utilization with the UUniFast algorithm
+//! by Bini and Buttazzo, and the acceptance ratio of a test is measured over
+//! many of them. Generation is driven by a small seedable pseudo-random
+//! generator, so that experiments are reproducible across platforms.
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        Rng,
+        TasksetGenerator,
+    };
+}
+
+/// SplitMix64 pseudo-random number generator. It is fast and has good
+/// statistical quality, but it is not suitable for cryptographic purposes.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+/// Generator of random implicit-deadline tasksets, whose periods are
+/// log-uniformly distributed in [min_period, max_period].
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct TasksetGenerator {
+    pub num_tasks: usize,
+    pub min_period: Time,
+    pub max_period: Time,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl TasksetGenerator {
+    pub fn new(num_tasks: usize, min_period: Time, max_period: Time) -> Self {
+        Self { num_tasks, min_period, max_period }
+    }
+
+    /// Random taskset with the given total utilization, sorted by increasing
+    /// period, i.e. in Rate Monotonic priority order.
+    pub fn generate(&self, total_utilization: f64, rng: &mut Rng) -> Vec<RTTask> {
+        let (log_min, log_max) = (self.min_period.as_nanos().ln(), self.max_period.as_nanos().ln());
+
+        let mut taskset: Vec<RTTask> = uunifast(self.num_tasks, total_utilization, rng).into_iter()
+            .map(|utilization| {
+                let period = Time::nanos(f64::exp(log_min + (log_max - log_min) * rng.next_f64()));
+
+                RTTask {
+                    wcet: period * utilization,
+                    deadline: period,
+                    period,
+                }
+            })
+            .collect();
+
+        taskset.sort_by_key(|task| task.period);
+        taskset
+    }
+}
+
+/// UUniFast (Bini and Buttazzo, 2005): `n` utilizations uniformly distributed
+/// among those summing to `total_utilization`.
+pub fn uunifast(n: usize, total_utilization: f64, rng: &mut Rng) -> Vec<f64> {
+    let mut utilizations = Vec::with_capacity(n);
+
+    let mut remaining = total_utilization;
+    for i in 1..n {
+        let next = remaining * rng.next_f64().powf(1.0 / (n - i) as f64);
+        utilizations.push(remaining - next);
+        remaining = next;
+    }
+
+    if n > 0 {
+        utilizations.push(remaining);
+    }
+
+    utilizations
+}
+
+/// `n` geometrically spaced periods, `base * ratio^i`, e.g. 1, 2, 4, 8 ms.
+/// They are harmonic when `ratio` is an integer.
+pub fn geometric_periods(base: Time, ratio: f64, n: usize) -> Vec<Time> {
+    (0..n)
+        .map(|i| base * ratio.powi(i as i32))
+        .collect()
+}
+
+/// Fraction of the `n` tasksets generated at each of the given utilizations
+/// which are accepted by the test. Zero for the utilizations at which no
+/// taskset is generated.
+pub fn acceptance_ratio(
+    u_values: &[f64],
+    n: usize,
+    generator: &TasksetGenerator,
+    test: impl Fn(&[RTTask]) -> bool,
+    rng: &mut Rng,
+) -> Vec<f64> {
+    u_values.iter()
+        .map(|&utilization| {
+            if n == 0 {
+                return 0.0;
+            }
+
+            let accepted = (0..n)
+                .filter(|_| test(&generator.generate(utilization, rng)))
+                .count();
+
+            accepted as f64 / n as f64
+        })
+        .collect()
+}
+
+/// Weighted schedulability (Bastoni et al., 2010): the acceptance ratios
+/// weighted by their utilization, `sum(u * ratio) / sum(u)`, which summarizes
+/// an acceptance ratio plot in a single number, rewarding the tests which
+/// accept tasksets at high utilizations. Zero if the utilizations sum to zero.
+///
+/// Panics if the two slices have different lengths.
+pub fn weighted_schedulability(u_values: &[f64], ratios: &[f64]) -> f64 {
+    assert_eq!(u_values.len(), ratios.len(), "Utilizations and ratios must have the same length");
+
+    let total: f64 = u_values.iter().sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    u_values.iter()
+        .zip(ratios)
+        .map(|(utilization, ratio)| utilization * ratio)
+        .sum::<f64>() / total
+}
+
+/// Deterministic taskset of `n` tasks for benchmarks, generated from the given
+/// seed with a total utilization of 0.75 and periods log-uniformly distributed
+/// between 1ms and 1s, as found in typical automotive workloads.
+pub fn bench_taskset(n: usize, seed: u64) -> Vec<RTTask> {
+    TasksetGenerator::new(n, Time::millis(1.0), Time::secs(1.0))
+        .generate(0.75, &mut Rng::new(seed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::RTUtils;
+
+    fn rta(taskset: &[RTTask]) -> bool {
+        RTUtils::response_time_fp(taskset).iter().all(Option::is_some)
+    }
+
+    #[test]
+    fn acceptance_ratio_is_deterministic_and_decreasing() {
+        let u_values = [0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+        let generator = TasksetGenerator::new(4, Time::millis(1.0), Time::millis(100.0));
+        let ratios = acceptance_ratio(&u_values, 200, &generator, rta, &mut Rng::new(162));
+
+        assert_eq!(ratios, acceptance_ratio(&u_values, 200, &generator, rta, &mut Rng::new(162)));
+
+        // Below the Liu-Layland bound every taskset is accepted.
+        assert_eq!(ratios[0], 1.0);
+        for pair in ratios.windows(2) {
+            assert!(pair[1] <= pair[0] + 0.05, "{ratios:?}");
+        }
+        assert!(ratios[5] < 0.5, "{ratios:?}");
+
+        assert_eq!(acceptance_ratio(&u_values, 0, &generator, rta, &mut Rng::new(162)), vec![0.0; 6]);
+    }
+
+    #[test]
+    fn weighted_schedulability_by_hand() {
+        // (0.5 * 1 + 0.75 * 0.8 + 1 * 0.2) / (0.5 + 0.75 + 1) = 1.3 / 2.25
+        let weighted = weighted_schedulability(&[0.5, 0.75, 1.0], &[1.0, 0.8, 0.2]);
+        assert!((weighted - 1.3 / 2.25).abs() < 1e-12);
+
+        assert_eq!(weighted_schedulability(&[0.2, 0.4], &[1.0, 1.0]), 1.0);
+        assert_eq!(weighted_schedulability(&[], &[]), 0.0);
+        assert_eq!(weighted_schedulability(&[0.0], &[1.0]), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn weighted_schedulability_rejects_mismatched_lengths() {
+        weighted_schedulability(&[0.5, 1.0], &[1.0]);
+    }
+
+    #[test]
+    fn geometric_periods_ratio_and_harmonicity() {
+        let chains = |periods: &[Time]| {
+            let taskset: Vec<RTTask> = periods.iter()
+                .map(|&period| RTTask::new(Time::zero(), period, period))
+                .collect();
+            RTUtils::harmonic_chains(&taskset).len()
+        };
+
+        let periods = geometric_periods(Time::millis(1.0), 2.0, 4);
+        assert_eq!(periods, [1.0, 2.0, 4.0, 8.0].map(Time::millis));
+        assert_eq!(chains(&periods), 1);
+
+        let periods = geometric_periods(Time::millis(2.0), 3.0, 5);
+        for pair in periods.windows(2) {
+            assert_eq!(pair[1] / pair[0], 3.0);
+        }
+        assert_eq!(chains(&periods), 1);
+
+        // Near-harmonic: 4, 6, 9 are pairwise non-harmonic
+        let periods = geometric_periods(Time::millis(4.0), 1.5, 3);
+        assert_eq!(periods, [4.0, 6.0, 9.0].map(Time::millis));
+        assert_eq!(chains(&periods), 3);
+
+        assert!(geometric_periods(Time::millis(1.0), 2.0, 0).is_empty());
+    }
+
+    #[test]
+    fn bench_taskset_is_deterministic() {
+        let taskset = bench_taskset(20, 195);
+
+        assert_eq!(taskset, bench_taskset(20, 195));
+        assert_ne!(taskset, bench_taskset(20, 196));
+
+        assert_eq!(taskset.len(), 20);
+        assert!((RTUtils::total_utilization(&taskset) - 0.75).abs() < 1e-9);
+        for task in &taskset {
+            assert!(task.period >= Time::millis(1.0) && task.period <= Time::secs(1.0), "{task:?}");
+        }
+    }
+}
@@ -8,12 +8,79 @@
 //! 
 //! 
 
-/// Prelude module with commonly used exports.
+/// Prelude module with commonly used exports. The `simulation` and
+/// `generation` modules have their own preludes, which are not included, as
+/// their generic names, e.g. `Rng` or `Job`, would easily clash with other
+/// crates.
 pub mod prelude {
+    pub use super::error::prelude::*;
     pub use super::time::prelude::*;
     pub use super::rt_task::prelude::*;
+    pub use super::rt_task_ext::prelude::*;
+    pub use super::multiframe_task::prelude::*;
+    pub use super::servers::prelude::*;
+    pub use super::prob_task::prelude::*;
+    pub use super::suspending_task::prelude::*;
+    pub use super::dag_task::prelude::*;
 }
 
+pub mod error;
 pub mod time;
 pub mod rt_task;
-pub mod utils;
\ No newline at end of file
+pub mod rt_task_ext;
+pub mod utils;
+pub mod simulation;
+pub mod multiframe_task;
+pub mod servers;
+pub mod prob_task;
+pub mod suspending_task;
+pub mod generation;
+pub mod dag_task;
+
+#[cfg(test)]
+mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::generation::{Rng, TasksetGenerator};
+    use crate::simulation::{Job, LlfScheduler, SimulationResult};
+
+    /// Every public struct can be built without struct literals, so that
+    /// fields can later be added without breaking downstream code.
+    #[test]
+    fn construction_through_methods_only() {
+        let ms = Time::millis(1.0);
+        assert_eq!(Time::nanos(1e6), ms);
+        assert_eq!(Time::micros(1e3), ms);
+        assert_eq!(Time::secs(1e-3), ms);
+        assert_eq!(Time::of(1.0, TimeUnit::Ms), ms);
+        assert_eq!(Time2::new(4.0).value(), Time2::from_time(Time::nanos(2.0)).value());
+        assert_eq!(TimeSpan::try_from(ms).unwrap().as_time(), ms);
+        assert_eq!(Interval::new(ms, ms * 3.0).length(), ms * 2.0);
+
+        let task = RTTask::new(ms, ms * 4.0, ms * 5.0);
+        assert_eq!((task.wcet(), task.deadline(), task.period()), (ms, ms * 4.0, ms * 5.0));
+        assert_eq!(Annotated::new(task.clone(), 7).data, 7);
+
+        let extended = RTTaskExt::new(task.clone(), ms * 0.5).with_offset(ms * 2.0).in_transaction(1);
+        assert_eq!((extended.bcet, extended.offset, extended.transaction), (ms * 0.5, ms * 2.0, Some(1)));
+        assert_eq!(SuspendingTask::new(task.clone(), ms).suspension, ms);
+
+        assert_eq!(MultiframeTask::new(vec![ms, ms * 2.0], ms * 5.0, ms * 5.0).max_wcet(), ms * 2.0);
+        assert_eq!(DagTask::new(vec![ms, ms], vec![(0, 1)], ms * 5.0, ms * 5.0).critical_path_length(), ms * 2.0);
+        assert_eq!(ProbTask::new(vec![(ms, 1e-9)], ms * 5.0, ms * 5.0).wcet_at(1e-9), Some(ms));
+
+        assert_eq!(PollingServer::new(ms, ms * 4.0).bandwidth(), 0.25);
+        assert_eq!(SporadicServer::new(ms, ms * 4.0).bandwidth(), 0.25);
+        assert_eq!(ServerState::new(ms, ms * 4.0).next_replenishment(), ms * 4.0);
+
+        let job = Job::new(1, ms, ms * 5.0, ms, false);
+        assert_eq!((job.task, job.release, job.deadline, job.remaining, job.started), (1, ms, ms * 5.0, ms, false));
+        assert_eq!(SimulationResult::new(vec![2], vec![1], ms).busy_time, ms);
+        assert_eq!(LlfScheduler::new(ms).quantum, ms);
+
+        let generator = TasksetGenerator::new(3, ms, ms * 10.0);
+        assert_eq!(generator.generate(0.5, &mut Rng::new(169)).len(), 3);
+    }
+}
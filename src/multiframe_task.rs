@@ -0,0 +1,190 @@
+//! MultiframeTask struct.
+//!
+//! This module defines the `MultiframeTask` struct, which describes a
+//! real-time task following the multiframe model by Mok and Chen. Unlike
+//! `RTTask`, the jobs of a multiframe task do not share the same execution
+//! time: the task cycles through a sequence of WCETs, one per job (e.g. the
+//! I/P/B frames of an MPEG stream).
+
+use crate::prelude::*;
+use crate::utils::RTUtils;
+
+pub mod prelude {
+    pub use super::{
+        MultiframeTask,
+    };
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct MultiframeTask {
+    /// Worst Case Execution Times of the frames, cyclically repeated
+    pub wcets: Vec<Time>,
+    /// Relative Deadline
+    pub deadline: Time,
+    /// (Minimum Inter-arrival) Period
+    pub period: Time,
+}
+
+impl MultiframeTask {
+    pub fn new(wcets: Vec<Time>, deadline: Time, period: Time) -> Self {
+        Self { wcets, deadline, period }
+    }
+
+    /// Largest WCET among the frames
+    pub fn max_wcet(&self) -> Time {
+        self.wcets.iter().copied().max().unwrap_or_default()
+    }
+
+    /// Sum of WCETs / (Frames * Period)
+    pub fn utilization(&self) -> f64 {
+        self.wcets.iter().copied().sum::<Time>() / (self.period * self.wcets.len() as f64)
+    }
+
+    /// Largest WCET / Period
+    pub fn peak_utilization(&self) -> f64 {
+        self.max_wcet() / self.period
+    }
+
+    /// Maximum demand of the jobs released in a window of the given length,
+    /// for the worst starting frame.
+    pub fn demand(&self, window: Time) -> Time {
+        let frames = self.wcets.len();
+        if frames == 0 || window <= Time::zero() {
+            return Time::zero();
+        }
+
+        let jobs = f64::ceil(window / self.period) as usize;
+        let cycles = (jobs / frames) as f64;
+        let partial = (0..frames)
+            .map(|start| (0..jobs % frames)
+                .map(|job| self.wcets[(start + job) % frames])
+                .sum::<Time>())
+            .max()
+            .unwrap_or_default();
+
+        self.wcets.iter().copied().sum::<Time>() * cycles + partial
+    }
+}
+
+impl RTUtils {
+    /// Response-time analysis for multiframe tasks under fixed priorities,
+    /// with tasks sorted by decreasing priority and constrained deadlines.
+    ///
+    /// Each higher priority task interferes with its worst-case accumulation
+    /// of consecutive frames, as given by `MultiframeTask::demand`, while the
+    /// analysed task executes its largest frame. Returns `None` for tasks
+    /// whose response time exceeds their deadline.
+    pub fn multiframe_response_time_fp(taskset: &[MultiframeTask]) -> Vec<Option<Time>> {
+        taskset.iter().enumerate()
+            .map(|(i, task)| {
+                let wcet = task.max_wcet();
+                let mut response = wcet;
+                loop {
+                    let next = wcet + taskset[..i].iter()
+                        .map(|higher| higher.demand(response))
+                        .sum();
+
+                    if next > task.deadline {
+                        return None;
+                    }
+
+                    if next == response {
+                        return Some(next);
+                    }
+
+                    response = next;
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn ns(value: f64) -> Time {
+        Time::nanos(value)
+    }
+
+    fn frames(wcets: &[f64]) -> Vec<Time> {
+        wcets.iter().copied().map(ns).collect()
+    }
+
+    #[test]
+    fn demand_of_alternating_frames() {
+        let task = MultiframeTask::new(frames(&[3.0, 1.0]), ns(4.0), ns(4.0));
+
+        assert_eq!(task.utilization(), 0.5);
+        assert_eq!(task.peak_utilization(), 0.75);
+        assert_eq!(task.demand(ns(0.0)), ns(0.0));
+        assert_eq!(task.demand(ns(4.0)), ns(3.0));
+        assert_eq!(task.demand(ns(5.0)), ns(4.0));
+        assert_eq!(task.demand(ns(9.0)), ns(7.0));
+        assert_eq!(task.demand(ns(13.0)), ns(8.0));
+    }
+
+    #[test]
+    fn multiframe_analysis_is_less_pessimistic_than_peak_wcet() {
+        // hand-computed: with the peak WCET the second task has response time
+        // 2 + 2 * 3 = 8 > 6, while the frames of the first task only
+        // accumulate 3 + 1 = 4 in any window of length 6
+        let taskset = [
+            MultiframeTask::new(frames(&[3.0, 1.0]), ns(4.0), ns(4.0)),
+            MultiframeTask::new(frames(&[2.0]), ns(6.0), ns(6.0)),
+        ];
+        assert_eq!(RTUtils::multiframe_response_time_fp(&taskset), vec![Some(ns(3.0)), Some(ns(6.0))]);
+
+        let peak = [RTTask::new_ns(3, 4, 4), RTTask::new_ns(2, 6, 6)];
+        assert_eq!(RTUtils::response_time_fp(&peak), vec![Some(ns(3.0)), None]);
+    }
+
+    #[test]
+    fn single_frame_tasks_match_plain_response_time() {
+        for taskset in random_tasksets(115, 2000) {
+            let mut taskset: Vec<RTTask> = taskset.into_iter()
+                .map(|task| RTTask { deadline: Time::min(task.deadline, task.period), ..task })
+                .collect();
+            taskset.sort_by_key(|task| task.deadline);
+
+            let multiframe: Vec<_> = taskset.iter()
+                .map(|task| MultiframeTask::new(vec![task.wcet], task.deadline, task.period))
+                .collect();
+
+            assert_eq!(RTUtils::multiframe_response_time_fp(&multiframe),
+                RTUtils::response_time_fp(&taskset), "{taskset:?}");
+        }
+    }
+
+    #[test]
+    fn frames_are_never_worse_than_the_peak_wcet() {
+        let mut rng = crate::generation::Rng::new(115);
+        for taskset in random_tasksets(116, 2000) {
+            let mut taskset: Vec<RTTask> = taskset.into_iter()
+                .map(|task| RTTask { deadline: Time::min(task.deadline, task.period), ..task })
+                .collect();
+            taskset.sort_by_key(|task| task.deadline);
+
+            // The WCET of each task is its first frame, the largest one
+            let multiframe: Vec<_> = taskset.iter()
+                .map(|task| {
+                    let peak = task.wcet.as_nanos() as u64;
+                    let wcets = std::iter::once(peak)
+                        .chain((0..3).map(|_| uniform(&mut rng, 0, peak)))
+                        .map(|wcet| ns(wcet as f64))
+                        .collect();
+                    MultiframeTask::new(wcets, task.deadline, task.period)
+                })
+                .collect();
+
+            let peak = RTUtils::response_time_fp(&taskset);
+            for (frames, peak) in RTUtils::multiframe_response_time_fp(&multiframe).into_iter().zip(peak) {
+                if let Some(peak) = peak {
+                    assert!(frames.is_some_and(|frames| frames <= peak), "{taskset:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! ProbTask struct.
+//!
+//! This module defines the `ProbTask` struct, which describes a real-time task
+//! whose execution time is given as a probability distribution, i.e. a
+//! probabilistic WCET (pWCET) as obtained from measurement-based analyses and
+//! Extreme Value Theory. The distribution is described by its Complementary
+//! Cumulative Distribution Function (CCDF), the exceedance probability of each
+//! execution time.
+//!
+//! A `ProbTask` is reduced to a deterministic `RTTask` by choosing a target
+//! exceedance probability, so that the existing analyses can be applied.
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        ProbTask,
+    };
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ProbTask {
+    /// Points `(c, P(X > c))` of the CCDF of the execution time, sorted by
+    /// increasing execution time and non-increasing probability
+    pub ccdf: Vec<(Time, f64)>,
+    /// Relative Deadline
+    pub deadline: Time,
+    /// (Minimum Inter-arrival) Period
+    pub period: Time,
+}
+
+impl ProbTask {
+    pub fn new(ccdf: Vec<(Time, f64)>, deadline: Time, period: Time) -> Self {
+        Self { ccdf, deadline, period }
+    }
+
+    /// Smallest execution time of the CCDF whose exceedance probability is at
+    /// most `probability`, e.g. the 1e-9 pWCET. Returns `None` if the CCDF does
+    /// not reach such a low probability.
+    pub fn wcet_at(&self, probability: f64) -> Option<Time> {
+        self.ccdf.iter()
+            .find(|&&(_, exceedance)| exceedance <= probability)
+            .map(|&(wcet, _)| wcet)
+    }
+
+    /// Deterministic task whose WCET is the pWCET at the given exceedance
+    /// probability, see `wcet_at`.
+    pub fn to_task(&self, probability: f64) -> Option<RTTask> {
+        Some(RTTask {
+            wcet: self.wcet_at(probability)?,
+            deadline: self.deadline,
+            period: self.period,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ProbTask {
+        let ccdf = [(1.0, 1.0), (1.2, 1e-3), (1.5, 1e-6), (1.8, 1e-9), (2.0, 1e-12)]
+            .map(|(wcet, exceedance)| (Time::millis(wcet), exceedance))
+            .to_vec();
+
+        ProbTask::new(ccdf, Time::millis(10.0), Time::millis(10.0))
+    }
+
+    #[test]
+    fn wcet_at_exceedance_probability() {
+        let task = sample();
+
+        assert_eq!(task.wcet_at(1e-9), Some(Time::millis(1.8)));
+        assert_eq!(task.wcet_at(2e-9), Some(Time::millis(1.8)));
+        assert_eq!(task.wcet_at(1e-10), Some(Time::millis(2.0)));
+        assert_eq!(task.wcet_at(1.0), Some(Time::millis(1.0)));
+        assert_eq!(task.wcet_at(1e-15), None);
+    }
+
+    #[test]
+    fn to_task_feeds_the_deterministic_model() {
+        let task = sample().to_task(1e-9).unwrap();
+
+        assert_eq!(task, RTTask::new(Time::millis(1.8), Time::millis(10.0), Time::millis(10.0)));
+        assert!(sample().to_task(1e-15).is_none());
+    }
+}
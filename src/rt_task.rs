@@ -10,6 +10,7 @@ use crate::prelude::*;
 pub mod prelude {
     pub use super::{
         RTTask,
+        Annotated,
     };
 }
 
@@ -24,7 +25,21 @@ pub struct RTTask {
     pub period: Time,
 }
 
+/// `RTTask` carrying additional user data, such as an identifier or a
+/// priority. It dereferences to the inner task, so that the `RTTask` methods
+/// can be called directly on it.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Annotated<T> {
+    pub task: RTTask,
+    pub data: T,
+}
+
 impl RTTask {
+    pub fn new(wcet: Time, deadline: Time, period: Time) -> Self {
+        Self { wcet, deadline, period }
+    }
+
     pub fn new_ns(wcet: u64, deadline: u64, period: u64) -> Self {
         Self {
             wcet: Time::nanos(wcet as f64),
@@ -33,6 +48,59 @@ impl RTTask {
         }
     }
 
+    /// Task with the given utilization, i.e. with WCET `u * period`.
+    ///
+    /// Panics if `u` is not in (0, 1].
+    pub fn from_utilization(u: f64, period: Time, deadline: Time) -> Self {
+        assert!(u > 0.0 && u <= 1.0, "Utilization must be in (0, 1]");
+
+        Self { wcet: period * u, deadline, period }
+    }
+
+    pub fn wcet(&self) -> Time {
+        self.wcet
+    }
+
+    pub fn deadline(&self) -> Time {
+        self.deadline
+    }
+
+    pub fn period(&self) -> Time {
+        self.period
+    }
+
+    /// Key to sort tasks by period, e.g. `sort_by_key(RTTask::period_key)`
+    pub fn period_key(&self) -> Time {
+        self.period
+    }
+
+    /// Key to sort tasks by deadline
+    pub fn deadline_key(&self) -> Time {
+        self.deadline
+    }
+
+    /// Key to sort tasks by utilization, totally ordered
+    pub fn utilization_key(&self) -> ordered_float::OrderedFloat<f64> {
+        ordered_float::OrderedFloat(self.utilization())
+    }
+
+    /// Copy of the task with the WCET increased by `penalty`, e.g. to account
+    /// for cache-related or memory contention delays.
+    pub fn inflate_wcet(&self, penalty: Time) -> RTTask {
+        RTTask {
+            wcet: self.wcet + penalty,
+            ..self.clone()
+        }
+    }
+
+    /// Copy of the task with the WCET multiplied by `factor`.
+    pub fn inflate_wcet_factor(&self, factor: f64) -> RTTask {
+        RTTask {
+            wcet: self.wcet * factor,
+            ..self.clone()
+        }
+    }
+
     /// WCET / Period
     pub fn utilization(&self) -> f64 {
         self.wcet.value_ns / self.period.value_ns
@@ -48,6 +116,17 @@ impl RTTask {
         self.deadline - self.wcet
     }
 
+    /// ceil(Window / Period) * WCET: the maximum execution time requested,
+    /// and thus the interference caused on lower priority tasks, by the jobs
+    /// released in a window of the given length.
+    pub fn interference(&self, over: Time) -> Time {
+        if over <= Time::zero() {
+            return Time::zero();
+        }
+
+        self.wcet * f64::ceil(over / self.period)
+    }
+
     /// Deadline == Period
     pub fn has_implicit_deadline(&self) -> bool {
         self.deadline == self.period
@@ -57,4 +136,354 @@ impl RTTask {
     pub fn has_constrained_deadline(&self) -> bool {
         self.deadline <= self.period
     }
-}
\ No newline at end of file
+
+    /// Time elapsed since the last release before or at `now`, with jobs
+    /// released periodically from time zero.
+    pub fn phase_at(&self, now: Time) -> Time {
+        now.rem_euclid(self.period)
+    }
+
+    /// First release strictly after `now`, with jobs released periodically
+    /// from time zero.
+    pub fn next_release_after(&self, now: Time) -> Time {
+        Time::max(now - self.phase_at(now) + self.period, Time::zero())
+    }
+
+    /// WCET <= Deadline and WCET <= Period: the necessary condition for the
+    /// task to be schedulable, even when executing alone.
+    pub fn is_trivially_feasible(&self) -> bool {
+        self.wcet <= self.deadline && self.wcet <= self.period
+    }
+}
+
+/// Task with zero WCET and a deadline and period of one nanosecond. Such a
+/// task requests no execution time, so it never affects the analyses, but
+/// it has valid, positive, deadline and period.
+impl Default for RTTask {
+    fn default() -> Self {
+        Self {
+            wcet: Time::zero(),
+            deadline: Time::one(),
+            period: Time::one(),
+        }
+    }
+}
+
+impl RTTask {
+    fn canonical_key(&self) -> [ordered_float::OrderedFloat<f64>; 3] {
+        [self.period, self.deadline, self.wcet]
+            .map(|time| ordered_float::OrderedFloat(time.round().value_ns))
+    }
+}
+
+impl PartialEq for RTTask {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_key() == other.canonical_key()
+    }
+}
+
+impl Eq for RTTask { }
+
+impl std::hash::Hash for RTTask {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.canonical_key().hash(state);
+    }
+}
+
+impl PartialOrd for RTTask {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Tasks are totally ordered lexicographically by period, deadline and WCET,
+/// each rounded to the nearest integer nanosecond. Equality and hashing use the
+/// same rounded values, so that `Eq`, `Ord` and `Hash` agree with each other.
+///
+/// Note that this differs from the tolerance-based equality of `Time`, which is
+/// not transitive: tasks whose parameters differ by less than half a
+/// nanosecond may still be different if they round to different values.
+impl Ord for RTTask {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.canonical_key().cmp(&other.canonical_key())
+    }
+}
+
+impl<T> Annotated<T> {
+    pub fn new(task: RTTask, data: T) -> Self {
+        Self { task, data }
+    }
+
+    pub fn into_inner(self) -> RTTask {
+        self.task
+    }
+
+    /// Copy of the inner tasks, in the same order, to be passed to the
+    /// taskset analyses. Results indexed by position can then be matched back
+    /// to the annotations.
+    pub fn tasks(annotated: &[Annotated<T>]) -> Vec<RTTask> {
+        annotated.iter()
+            .map(|annotated| annotated.task.clone())
+            .collect()
+    }
+}
+
+impl<T> std::ops::Deref for Annotated<T> {
+    type Target = RTTask;
+
+    fn deref(&self) -> &Self::Target {
+        &self.task
+    }
+}
+
+impl<T> std::ops::DerefMut for Annotated<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.task
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::RTUtils;
+
+    #[test]
+    fn ordering_is_by_period_deadline_and_wcet() {
+        let mut tasks = vec![
+            RTTask::new_ns(1, 10, 10),
+            RTTask::new_ns(2, 5, 10),
+            RTTask::new_ns(1, 5, 10),
+            RTTask::new_ns(9, 9, 9),
+        ];
+        tasks.sort();
+
+        assert_eq!(tasks, vec![
+            RTTask::new_ns(9, 9, 9),
+            RTTask::new_ns(1, 5, 10),
+            RTTask::new_ns(2, 5, 10),
+            RTTask::new_ns(1, 10, 10),
+        ]);
+    }
+
+    #[test]
+    fn interference_at_period_boundaries() {
+        let task = RTTask::new_ns(2, 5, 5);
+
+        assert_eq!(task.interference(Time::zero()), Time::zero());
+        assert_eq!(task.interference(Time::nanos(-1.0)), Time::zero());
+        assert_eq!(task.interference(Time::nanos(1.0)), Time::nanos(2.0));
+        assert_eq!(task.interference(Time::nanos(5.0)), Time::nanos(2.0));
+        assert_eq!(task.interference(Time::nanos(5.5)), Time::nanos(4.0));
+        assert_eq!(task.interference(Time::nanos(10.0)), Time::nanos(4.0));
+        assert_eq!(task.interference(Time::nanos(11.0)), Time::nanos(6.0));
+    }
+
+    #[test]
+    fn near_equal_tasks_in_a_set() {
+        let task = |wcet: f64, period: f64|
+            RTTask::new(Time::nanos(wcet), Time::nanos(period), Time::nanos(period));
+
+        let tasks = [
+            task(1.0, 10.0),
+            task(1.3, 10.0),
+            task(1.0, 10.2),
+            task(0.8, 9.9),
+            task(1.0, 10.6),
+            task(1.6, 10.0),
+        ];
+
+        let hashed: std::collections::HashSet<_> = tasks.iter().cloned().collect();
+        let ordered: std::collections::BTreeSet<_> = tasks.iter().cloned().collect();
+        assert_eq!(hashed.len(), 3);
+        assert_eq!(ordered.len(), 3);
+
+        for a in &tasks {
+            for b in &tasks {
+                assert_eq!(a == b, a.cmp(b) == std::cmp::Ordering::Equal);
+            }
+        }
+
+        // Within the `Time` tolerance, but rounded to different nanoseconds.
+        assert_ne!(task(1.4, 10.0), task(1.6, 10.0));
+    }
+
+    #[test]
+    fn annotated_response_times_keyed_by_name() {
+        let annotated = vec![
+            Annotated::new(RTTask::new_ns(1, 4, 4), "sensor"),
+            Annotated::new(RTTask::new_ns(2, 6, 6), "control"),
+            Annotated::new(RTTask::new_ns(3, 12, 12), "logger"),
+        ];
+        assert_eq!(annotated[0].utilization(), 0.25);
+
+        let response_times: std::collections::HashMap<_, _> = annotated.iter()
+            .map(|annotated| annotated.data)
+            .zip(RTUtils::response_time_fp(&Annotated::tasks(&annotated)))
+            .collect();
+
+        assert_eq!(response_times["sensor"], Some(Time::nanos(1.0)));
+        assert_eq!(response_times["control"], Some(Time::nanos(3.0)));
+        assert_eq!(response_times["logger"], Some(Time::nanos(10.0)));
+
+        let mut logger = annotated.into_iter().last().unwrap();
+        logger.wcet = Time::nanos(4.0);
+        assert_eq!(logger.into_inner(), RTTask::new_ns(4, 12, 12));
+    }
+
+    #[test]
+    fn trivially_feasible_conditions() {
+        assert!(RTTask::new_ns(3, 5, 10).is_trivially_feasible());
+        assert!(RTTask::new_ns(5, 5, 5).is_trivially_feasible());
+        assert!(RTTask::default().is_trivially_feasible());
+
+        // WCET longer than the deadline
+        assert!(!RTTask::new_ns(6, 5, 10).is_trivially_feasible());
+        // WCET longer than the period, with an arbitrary deadline
+        assert!(!RTTask::new_ns(6, 20, 5).is_trivially_feasible());
+        // Both
+        assert!(!RTTask::new_ns(12, 5, 10).is_trivially_feasible());
+    }
+
+    #[test]
+    fn default_values() {
+        assert_eq!(Time::default().value_ns, 0.0);
+
+        let task = RTTask::default();
+        assert_eq!(task.wcet.value_ns, 0.0);
+        assert_eq!(task.deadline.value_ns, 1.0);
+        assert_eq!(task.period.value_ns, 1.0);
+        assert_eq!(task.utilization(), 0.0);
+        assert!(task.has_implicit_deadline());
+        assert!(RTUtils::validate(&[task]).is_ok());
+    }
+
+    #[test]
+    fn accessors_match_the_fields() {
+        let task = RTTask::new(Time::millis(1.0), Time::millis(4.0), Time::millis(5.0));
+
+        assert_eq!(task.wcet(), task.wcet);
+        assert_eq!(task.deadline(), Time::millis(4.0));
+        assert_eq!(task.period(), Time::millis(5.0));
+        assert_eq!(task.period_key(), task.period());
+        assert_eq!(task.deadline_key(), task.deadline());
+        assert_eq!(task.utilization_key(), ordered_float::OrderedFloat(0.2));
+    }
+
+    #[test]
+    fn sorting_by_each_key() {
+        let tasks = vec![
+            RTTask::new_ns(3, 6, 12),
+            RTTask::new_ns(1, 9, 10),
+            RTTask::new_ns(2, 4, 4),
+        ];
+
+        let mut by_period = tasks.clone();
+        by_period.sort_by_key(RTTask::period_key);
+        assert_eq!(by_period, vec![tasks[2].clone(), tasks[1].clone(), tasks[0].clone()]);
+
+        let mut by_deadline = tasks.clone();
+        by_deadline.sort_by_key(RTTask::deadline_key);
+        assert_eq!(by_deadline, vec![tasks[2].clone(), tasks[0].clone(), tasks[1].clone()]);
+
+        let mut by_utilization = tasks.clone();
+        by_utilization.sort_by_key(RTTask::utilization_key);
+        assert_eq!(by_utilization, vec![tasks[1].clone(), tasks[0].clone(), tasks[2].clone()]);
+    }
+
+    #[test]
+    fn phase_and_next_release() {
+        let task = RTTask::new_ns(1, 10, 10);
+        let ns = Time::nanos;
+
+        // Exactly at a release
+        assert_eq!(task.phase_at(ns(0.0)), ns(0.0));
+        assert_eq!(task.phase_at(ns(20.0)), ns(0.0));
+        assert_eq!(task.next_release_after(ns(0.0)), ns(10.0));
+        assert_eq!(task.next_release_after(ns(20.0)), ns(30.0));
+
+        // Mid-period
+        assert_eq!(task.phase_at(ns(25.0)), ns(5.0));
+        assert_eq!(task.phase_at(ns(29.75)).value_ns, 9.75);
+        assert_eq!(task.next_release_after(ns(25.0)), ns(30.0));
+
+        // Before time zero, the first release is still at zero
+        assert_eq!(task.phase_at(ns(-3.0)), ns(7.0));
+        assert_eq!(task.next_release_after(ns(-3.0)), ns(0.0));
+        assert_eq!(task.next_release_after(ns(-13.0)), ns(0.0));
+
+        // Sub-nanosecond periods are not rounded down
+        let fractional = RTTask::new(ns(0.1), ns(0.25), ns(0.25));
+        assert_eq!(fractional.phase_at(ns(1.125)).value_ns, 0.125);
+        assert_eq!(fractional.next_release_after(ns(1.0)).value_ns, 1.25);
+    }
+
+    #[test]
+    fn flattened_into_a_config() {
+        use crate::test_utils::Value;
+
+        #[derive(serde::Deserialize)]
+        struct Config {
+            id: String,
+            name: String,
+            #[serde(flatten)]
+            task: RTTask,
+        }
+
+        let field = |key: &str, value: Value| (key.to_string(), value);
+        let config = |wcet: Value| Value::Map(vec![
+            field("id", Value::Str("t7".to_string())),
+            field("name", Value::Str("control".to_string())),
+            field("wcet", wcet),
+            field("deadline", Value::Str("5ns".to_string())),
+            field("period", Value::Str("10ns".to_string())),
+        ]).deserialize::<Config>();
+
+        let parsed = config(Value::Str("2".to_string())).unwrap();
+        assert_eq!(parsed.id, "t7");
+        assert_eq!(parsed.name, "control");
+        assert_eq!(parsed.task, RTTask::new_ns(2, 5, 10));
+
+        // Times are strings, also when flattened
+        assert!(config(Value::F64(2.0)).is_err());
+    }
+
+    #[test]
+    fn inflated_wcet_raises_the_utilization() {
+        let task = RTTask::new_ns(2, 8, 10);
+
+        let penalized = task.inflate_wcet(Time::nanos(1.0));
+        assert_eq!(penalized, RTTask::new_ns(3, 8, 10));
+        assert_eq!(penalized.utilization(), 0.3);
+        assert_eq!(penalized.density(), 3.0 / 8.0);
+
+        let scaled = task.inflate_wcet_factor(1.5);
+        assert_eq!(scaled, RTTask::new_ns(3, 8, 10));
+        assert!((scaled.utilization() - 1.5 * task.utilization()).abs() < 1e-12);
+
+        assert_eq!(task.inflate_wcet(Time::zero()), task);
+        assert_eq!(task.inflate_wcet_factor(1.0), task);
+    }
+
+    #[test]
+    fn from_utilization_matches_the_utilization() {
+        let task = RTTask::from_utilization(0.25, Time::millis(8.0), Time::millis(6.0));
+        assert_eq!(task, RTTask::new(Time::millis(2.0), Time::millis(6.0), Time::millis(8.0)));
+
+        for u in [0.01, 0.1, 0.3, 0.7, 1.0] {
+            let task = RTTask::from_utilization(u, Time::millis(10.0), Time::millis(10.0));
+            assert!((task.utilization() - u).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Utilization must be in (0, 1]")]
+    fn from_utilization_above_one() {
+        RTTask::from_utilization(1.5, Time::millis(10.0), Time::millis(10.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Utilization must be in (0, 1]")]
+    fn from_zero_utilization() {
+        RTTask::from_utilization(0.0, Time::millis(10.0), Time::millis(10.0));
+    }
+}
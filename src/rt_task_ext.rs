@@ -0,0 +1,377 @@
+//! RTTaskExt struct.
+//!
+//! This module defines the `RTTaskExt` struct, which extends the Liu-Layland
+//! `RTTask` with the **Best Case Execution Time** (BCET), so that jobs execute
+//! for any time in the range [BCET, WCET]. This enables the computation of
+//! best-case response times and thus of response-time jitter bounds.
+//!
+//! Tasks may also have an **Offset**, the release time of their first job.
+//! Following the transaction model by Tindell, tasks can be grouped, by an
+//! identifier, in transactions of tasks with the same period, whose offsets
+//! are relative to each other, so that offset-aware analyses know which jobs
+//! cannot be released together. Tasks outside of any transaction are
+//! independent, and their offsets carry no such information.
+
+use crate::prelude::*;
+use crate::utils::{RTUtils, MAX_RESPONSE_TIME_ITERATIONS};
+
+pub mod prelude {
+    pub use super::{
+        RTTaskExt,
+    };
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct RTTaskExt {
+    pub task: RTTask,
+    /// Best Case Execution Time
+    pub bcet: Time,
+    /// Release time of the first job
+    #[serde(default)]
+    pub offset: Time,
+    /// Transaction of the task, if any
+    #[serde(default)]
+    pub transaction: Option<usize>,
+}
+
+impl RTTaskExt {
+    /// Task with zero offset, outside of any transaction.
+    pub fn new(task: RTTask, bcet: Time) -> Self {
+        Self { task, bcet, offset: Time::zero(), transaction: None }
+    }
+
+    pub fn with_offset(self, offset: Time) -> Self {
+        Self { offset, ..self }
+    }
+
+    pub fn in_transaction(self, transaction: usize) -> Self {
+        Self { transaction: Some(transaction), ..self }
+    }
+
+    /// Copy of the inner tasks, in the same order, to be passed to the
+    /// taskset analyses.
+    pub fn tasks(taskset: &[RTTaskExt]) -> Vec<RTTask> {
+        taskset.iter()
+            .map(|task| task.task.clone())
+            .collect()
+    }
+
+    /// Task executing every job for its BCET.
+    fn best_case(&self) -> RTTask {
+        RTTask {
+            wcet: self.bcet,
+            ..self.task.clone()
+        }
+    }
+
+    /// Release time of the first job of this task at or after a release of
+    /// the given task of the same transaction, relative to the latter.
+    fn phase_from(&self, other: &RTTaskExt) -> Time {
+        (self.offset - other.offset).rem_euclid(self.task.period)
+    }
+
+    /// Execution time requested by the jobs released in [0, window), when a
+    /// job of the given task of the same transaction is released at zero.
+    fn interference_from(&self, other: &RTTaskExt, window: Time) -> Time {
+        self.task.interference(window - self.phase_from(other))
+    }
+}
+
+impl RTUtils {
+    /// Best-case response time of each task under fixed priorities, with tasks
+    /// sorted by decreasing priority (Redell and Sanfridson, 2002).
+    ///
+    /// In the best case a job is released just as the higher priority tasks
+    /// complete, and all the jobs execute for their BCET, so that only the
+    /// jobs released strictly inside its execution interfere with it:
+    /// `R = B_i + sum_j ceil(R / T_j - 1)_0 * B_j`. The recurrence is iterated
+    /// downwards from the worst-case response time, so `None` is returned for
+    /// the tasks whose worst-case response time exceeds their deadline.
+    pub fn best_case_response_time(taskset: &[RTTaskExt]) -> Vec<Option<Time>> {
+        let worst_case = Self::response_time_fp(&RTTaskExt::tasks(taskset));
+
+        taskset.iter().zip(worst_case)
+            .enumerate()
+            .map(|(i, (task, worst_case))| {
+                let mut response = worst_case?;
+                loop {
+                    let next = task.bcet + taskset[..i].iter()
+                        .map(|higher| higher.best_case().interference(response - higher.task.period))
+                        .sum();
+
+                    if next == response {
+                        return Some(next);
+                    }
+
+                    response = next;
+                }
+            })
+            .collect()
+    }
+
+    /// Response-time jitter of each task under fixed priorities, with tasks
+    /// sorted by decreasing priority: the difference between the worst-case
+    /// and the best-case response times. Returns `None` for the tasks whose
+    /// worst-case response time exceeds their deadline.
+    pub fn response_time_jitter(taskset: &[RTTaskExt]) -> Vec<Option<Time>> {
+        let worst_case = Self::response_time_fp(&RTTaskExt::tasks(taskset));
+        let best_case = Self::best_case_response_time(taskset);
+
+        worst_case.into_iter().zip(best_case)
+            .map(|(worst_case, best_case)| Some(worst_case? - best_case?))
+            .collect()
+    }
+
+    /// Worst-case response time of each task under fixed priorities, with
+    /// tasks sorted by decreasing priority and constrained deadlines, for
+    /// tasks with offsets (Tindell, 1992; Palencia and Harbour, 1998,
+    /// without release jitter).
+    ///
+    /// Only the tasks with the same `transaction` identifier are assumed to
+    /// have fixed relative offsets; a task outside of any transaction may be
+    /// released at any time relative to the others, as if its offset were
+    /// zero. In a transaction the critical instant is the release of one of
+    /// the higher priority tasks, or of the analysed task itself. All the
+    /// candidate instants are examined for the transaction of the analysed
+    /// task, while the other transactions contribute the maximum interference
+    /// over their candidate instants, an upper bound of the exact analysis.
+    /// With zero offsets, or no transactions, it is the same as
+    /// `response_time_fp`. Returns `None` for the tasks whose response time
+    /// exceeds their deadline.
+    ///
+    /// Panics if the tasks of a transaction do not have the same period.
+    pub fn worst_case_response_offset(taskset: &[RTTaskExt]) -> Vec<Option<Time>> {
+        let same_period = |a: &RTTaskExt, b: &RTTaskExt| {
+            a.transaction.is_none() || a.transaction != b.transaction || a.task.period == b.task.period
+        };
+        assert!(taskset.iter().all(|a| taskset.iter().all(|b| same_period(a, b))),
+            "Tasks of a transaction must have the same period");
+
+        (0..taskset.len())
+            .map(|i| Self::offset_response_time(taskset, i))
+            .collect()
+    }
+
+    fn offset_response_time(taskset: &[RTTaskExt], i: usize) -> Option<Time> {
+        let task = &taskset[i];
+        if Self::total_utilization(&RTTaskExt::tasks(&taskset[..=i])) > 1.0 {
+            return None;
+        }
+
+        // Independent tasks are transactions of their own
+        let mut transactions: Vec<Vec<&RTTaskExt>> = Vec::new();
+        for higher in &taskset[..i] {
+            let members = higher.transaction.and_then(|transaction| transactions.iter_mut()
+                .find(|members| members[0].transaction == Some(transaction)));
+
+            match members {
+                Some(members) => members.push(higher),
+                None => transactions.push(vec![higher]),
+            }
+        }
+
+        let own_position = task.transaction.and_then(|transaction| transactions.iter()
+            .position(|members| members[0].transaction == Some(transaction)));
+        let own = own_position
+            .map(|position| transactions.remove(position))
+            .unwrap_or_default();
+
+        let other_interference = |window: Time| -> Time {
+            transactions.iter()
+                .map(|members| members.iter()
+                    .map(|critical| members.iter()
+                        .map(|member| member.interference_from(critical, window))
+                        .sum::<Time>())
+                    .max()
+                    .unwrap_or_default())
+                .sum()
+        };
+
+        let mut iterations = 0;
+        let mut worst = Time::zero();
+        for critical in own.iter().copied().chain(std::iter::once(task)) {
+            let first_release = task.phase_from(critical);
+
+            for job in 0.. {
+                let job = job as f64;
+                let release = first_release + task.task.period * job;
+
+                let demand = |window: Time| -> Time {
+                    task.task.wcet * (job + 1.0)
+                        + other_interference(window)
+                        + own.iter()
+                            .map(|member| member.interference_from(critical, window))
+                            .sum()
+                };
+
+                let mut finish = task.task.wcet * (job + 1.0);
+                loop {
+                    let next = demand(finish);
+                    iterations += 1;
+                    if next - release > task.task.deadline || iterations > MAX_RESPONSE_TIME_ITERATIONS {
+                        return None;
+                    }
+
+                    if next == finish {
+                        break;
+                    }
+
+                    finish = next;
+                }
+
+                // The busy period ends before the job is released
+                if finish <= release {
+                    break;
+                }
+
+                worst = Time::max(worst, finish - release);
+                if finish <= release + task.task.period {
+                    break;
+                }
+            }
+        }
+
+        Some(worst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::Rng;
+    use crate::test_utils::*;
+
+    fn ns(value: f64) -> Time {
+        Time::nanos(value)
+    }
+
+    fn task_ext(wcet: u64, bcet: u64, deadline: u64, period: u64) -> RTTaskExt {
+        RTTaskExt::new(RTTask::new_ns(wcet, deadline, period), ns(bcet as f64))
+    }
+
+    /// Random tasksets with a BCET in [0, WCET] for each task.
+    fn random_tasksets_ext(seed: u64, count: usize) -> Vec<Vec<RTTaskExt>> {
+        let mut rng = Rng::new(seed);
+
+        random_tasksets(seed, count).into_iter()
+            .map(|taskset| taskset.into_iter()
+                .map(|task| {
+                    let bcet = uniform(&mut rng, 0, task.wcet.as_nanos() as u64);
+                    RTTaskExt::new(task, ns(bcet as f64))
+                })
+                .collect())
+            .collect()
+    }
+
+    #[test]
+    fn best_case_response_time_range() {
+        let taskset = [
+            task_ext(1, 1, 4, 4),
+            task_ext(2, 1, 6, 6),
+            task_ext(3, 2, 12, 12),
+        ];
+
+        assert_eq!(RTUtils::response_time_fp(&RTTaskExt::tasks(&taskset)),
+            vec![Some(ns(1.0)), Some(ns(3.0)), Some(ns(10.0))]);
+        // R3: 10 -> 5 -> 3 -> 2
+        assert_eq!(RTUtils::best_case_response_time(&taskset),
+            vec![Some(ns(1.0)), Some(ns(1.0)), Some(ns(2.0))]);
+        assert_eq!(RTUtils::response_time_jitter(&taskset),
+            vec![Some(ns(0.0)), Some(ns(2.0)), Some(ns(8.0))]);
+
+        let overloaded = [task_ext(3, 1, 4, 4), task_ext(2, 1, 4, 4)];
+        assert_eq!(RTUtils::best_case_response_time(&overloaded), vec![Some(ns(1.0)), None]);
+    }
+
+    #[test]
+    fn best_case_is_not_above_worst_case() {
+        for mut taskset in random_tasksets_ext(146, 3000) {
+            taskset.sort_by_key(|task| task.task.period);
+            let worst_case = RTUtils::response_time_fp(&RTTaskExt::tasks(&taskset));
+            let best_case = RTUtils::best_case_response_time(&taskset);
+
+            for ((task, worst_case), best_case) in taskset.iter().zip(worst_case).zip(best_case) {
+                assert_eq!(worst_case.is_some(), best_case.is_some());
+                if let (Some(worst_case), Some(best_case)) = (worst_case, best_case) {
+                    assert!(task.bcet <= best_case && best_case <= worst_case, "{taskset:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn response_time_jitter_of_a_small_taskset() {
+        // WCRT 2 and 5, BCRT 1 and 2
+        let taskset = [task_ext(2, 1, 5, 5), task_ext(3, 2, 12, 12)];
+        assert_eq!(RTUtils::response_time_jitter(&taskset), vec![Some(ns(1.0)), Some(ns(3.0))]);
+
+        // With fixed execution times, only the interference varies.
+        let fixed = [task_ext(2, 2, 5, 5), task_ext(3, 3, 12, 12)];
+        assert_eq!(RTUtils::response_time_jitter(&fixed), vec![Some(ns(0.0)), Some(ns(2.0))]);
+
+        let infeasible = [task_ext(2, 1, 5, 5), task_ext(3, 2, 4, 12)];
+        assert_eq!(RTUtils::response_time_jitter(&infeasible), vec![Some(ns(1.0)), None]);
+    }
+
+    #[test]
+    fn offsets_reduce_the_interference() {
+        // All in the same transaction: the second task is released halfway
+        // through the period, so it never interferes with the third one.
+        let synchronous = [
+            task_ext(2, 2, 10, 10).in_transaction(0),
+            task_ext(2, 2, 10, 10).in_transaction(0),
+            task_ext(3, 3, 10, 10).in_transaction(0),
+        ];
+        let mut offset = synchronous.clone();
+        offset[1] = offset[1].clone().with_offset(ns(5.0));
+
+        assert_eq!(RTUtils::worst_case_response_offset(&synchronous),
+            vec![Some(ns(2.0)), Some(ns(4.0)), Some(ns(7.0))]);
+        assert_eq!(RTUtils::worst_case_response_offset(&offset),
+            vec![Some(ns(2.0)), Some(ns(2.0)), Some(ns(5.0))]);
+
+        // Offsets of a transaction are relative to each other.
+        let shifted: Vec<_> = offset.iter()
+            .map(|task| task.clone().with_offset(task.offset + ns(3.0)))
+            .collect();
+        assert_eq!(RTUtils::worst_case_response_offset(&shifted),
+            RTUtils::worst_case_response_offset(&offset));
+
+        // Independent tasks with the same period may be released together.
+        let independent: Vec<_> = offset.iter()
+            .map(|task| RTTaskExt { transaction: None, ..task.clone() })
+            .collect();
+        assert_eq!(RTUtils::worst_case_response_offset(&independent),
+            RTUtils::worst_case_response_offset(&synchronous));
+
+        // Only the second task is related to the third one.
+        let mut partial = offset.clone();
+        partial[0].transaction = Some(1);
+        assert_eq!(RTUtils::worst_case_response_offset(&partial),
+            vec![Some(ns(2.0)), Some(ns(4.0)), Some(ns(5.0))]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tasks of a transaction must have the same period")]
+    fn transactions_have_a_single_period() {
+        let taskset = [task_ext(1, 1, 5, 5).in_transaction(0), task_ext(1, 1, 10, 10).in_transaction(0)];
+        RTUtils::worst_case_response_offset(&taskset);
+    }
+
+    #[test]
+    fn zero_offsets_match_plain_response_time() {
+        for taskset in random_tasksets_ext(175, 3000) {
+            let mut taskset: Vec<_> = taskset.into_iter()
+                .map(|mut task| {
+                    task.task.deadline = Time::min(task.task.deadline, task.task.period);
+                    task
+                })
+                .collect();
+            taskset.sort_by_key(|task| task.task.deadline);
+
+            assert_eq!(RTUtils::worst_case_response_offset(&taskset),
+                RTUtils::response_time_fp(&RTTaskExt::tasks(&taskset)), "{taskset:?}");
+        }
+    }
+}
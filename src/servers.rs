@@ -0,0 +1,197 @@
+//! Aperiodic servers.
+//!
+//! This module defines reservation servers, characterized by a **Budget**
+//! replenished every **Period**. Servers which, from the point of view of the
+//! other tasks, behave like a periodic task with WCET equal to the budget and
+//! implicit deadline can be converted into an `RTTask`, so that they take part
+//! in the taskset analyses.
+//!
+//! Deferrable servers are not equivalent to periodic tasks, as they can
+//! execute back-to-back across a replenishment, and thus are not convertible.
+//!
+//! The run-time budget of a server, e.g. in a simulation, is tracked by a
+//! `ServerState`.
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        PollingServer,
+        SporadicServer,
+        ServerState,
+    };
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct PollingServer {
+    /// Execution budget
+    pub budget: Time,
+    /// Replenishment period
+    pub period: Time,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SporadicServer {
+    /// Execution budget
+    pub budget: Time,
+    /// Replenishment period
+    pub period: Time,
+}
+
+/// Run-time state of a server: the budget left in the current period and the
+/// time of the next replenishment. The remaining budget is always between
+/// zero and the full budget.
+#[derive(Debug, Clone)]
+pub struct ServerState {
+    budget: Time,
+    period: Time,
+    budget_remaining: Time,
+    next_replenishment: Time,
+}
+
+impl PollingServer {
+    pub fn new(budget: Time, period: Time) -> Self {
+        Self { budget, period }
+    }
+
+    /// Budget / Period
+    pub fn bandwidth(&self) -> f64 {
+        self.budget / self.period
+    }
+}
+
+impl SporadicServer {
+    pub fn new(budget: Time, period: Time) -> Self {
+        Self { budget, period }
+    }
+
+    /// Budget / Period
+    pub fn bandwidth(&self) -> f64 {
+        self.budget / self.period
+    }
+}
+
+impl ServerState {
+    /// State at time zero, with full budget and the first replenishment one
+    /// period later.
+    pub fn new(budget: Time, period: Time) -> Self {
+        Self {
+            budget,
+            period,
+            budget_remaining: budget,
+            next_replenishment: period,
+        }
+    }
+
+    pub fn budget_remaining(&self) -> Time {
+        self.budget_remaining
+    }
+
+    pub fn next_replenishment(&self) -> Time {
+        self.next_replenishment
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.budget_remaining <= Time::zero()
+    }
+
+    /// Consume up to the given execution time from the remaining budget, and
+    /// return the time actually consumed. Negative times consume nothing.
+    pub fn consume(&mut self, time: Time) -> Time {
+        let consumed = Time::min(Time::max(time, Time::zero()), self.budget_remaining);
+        self.budget_remaining = self.budget_remaining - consumed;
+        consumed
+    }
+
+    /// Restore the full budget, and schedule the next replenishment one
+    /// period after the current one.
+    pub fn replenish(&mut self) {
+        self.budget_remaining = self.budget;
+        self.next_replenishment = self.next_replenishment + self.period;
+    }
+}
+
+impl From<&PollingServer> for ServerState {
+    fn from(server: &PollingServer) -> Self {
+        Self::new(server.budget, server.period)
+    }
+}
+
+impl From<&SporadicServer> for ServerState {
+    fn from(server: &SporadicServer) -> Self {
+        Self::new(server.budget, server.period)
+    }
+}
+
+impl From<PollingServer> for RTTask {
+    fn from(server: PollingServer) -> Self {
+        Self {
+            wcet: server.budget,
+            deadline: server.period,
+            period: server.period,
+        }
+    }
+}
+
+impl From<SporadicServer> for RTTask {
+    fn from(server: SporadicServer) -> Self {
+        Self {
+            wcet: server.budget,
+            deadline: server.period,
+            period: server.period,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converted_task_utilization_is_the_bandwidth() {
+        let polling = PollingServer::new(Time::millis(2.0), Time::millis(8.0));
+        let sporadic = SporadicServer::new(Time::micros(300.0), Time::millis(1.0));
+
+        let task = RTTask::from(polling.clone());
+        assert_eq!(task.utilization(), polling.bandwidth());
+        assert_eq!(task.wcet, polling.budget);
+        assert!(task.has_implicit_deadline());
+
+        let task = RTTask::from(sporadic.clone());
+        assert_eq!(task.utilization(), sporadic.bandwidth());
+        assert_eq!(task.period, sporadic.period);
+        assert!(task.has_implicit_deadline());
+    }
+
+    #[test]
+    fn server_state_consume_and_replenish() {
+        let server = PollingServer::new(Time::millis(2.0), Time::millis(10.0));
+        let mut state = ServerState::from(&server);
+        assert_eq!(state.budget_remaining(), Time::millis(2.0));
+        assert_eq!(state.next_replenishment(), Time::millis(10.0));
+
+        assert_eq!(state.consume(Time::millis(0.5)), Time::millis(0.5));
+        assert_eq!(state.budget_remaining(), Time::millis(1.5));
+        assert_eq!(state.consume(Time::millis(-1.0)), Time::zero());
+        assert!(!state.is_exhausted());
+
+        // The budget never becomes negative.
+        assert_eq!(state.consume(Time::millis(5.0)), Time::millis(1.5));
+        assert_eq!(state.budget_remaining(), Time::zero());
+        assert!(state.is_exhausted());
+        assert_eq!(state.consume(Time::millis(1.0)), Time::zero());
+
+        for cycle in 2..5 {
+            state.replenish();
+            assert_eq!(state.budget_remaining(), Time::millis(2.0));
+            assert_eq!(state.next_replenishment(), Time::millis(10.0 * cycle as f64));
+            assert_eq!(state.consume(Time::millis(2.0)), Time::millis(2.0));
+            assert!(state.is_exhausted());
+        }
+
+        let sporadic = SporadicServer::new(Time::millis(1.0), Time::millis(4.0));
+        assert_eq!(ServerState::from(&sporadic).budget_remaining(), Time::millis(1.0));
+    }
+}
@@ -0,0 +1,534 @@
+//! Uniprocessor schedule simulation.
+//!
+//! This module simulates the execution of a taskset on a single processor in
+//! the worst-case scenario: all tasks are released synchronously at time zero,
+//! jobs arrive strictly periodically and always execute for their full WCET.
+//! The scheduling policy is pluggable through the `Scheduler` trait.
+//!
+//! Jobs which reach their absolute deadline without having completed are
+//! counted as deadline misses and aborted, so that an overloaded taskset does
+//! not accumulate an unbounded backlog.
+//!
+//! The events happening during the simulation (releases, preemptions,
+//! completions, ...) can be collected through the `Observer` trait, e.g. by a
+//! `Timeline`, to decouple the simulation from the rendering of the schedule.
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        Job,
+        Scheduler,
+        EdfScheduler,
+        RmScheduler,
+        LlfScheduler,
+        SimulationResult,
+        TaskId,
+        EventKind,
+        Observer,
+        Timeline,
+    };
+}
+
+/// Pending job of a simulated task.
+#[derive(Debug, Clone)]
+pub struct Job {
+    /// Index of the generating task in the taskset
+    pub task: usize,
+    /// Absolute release time
+    pub release: Time,
+    /// Absolute deadline
+    pub deadline: Time,
+    /// Remaining execution time
+    pub remaining: Time,
+    /// Whether the job has already been scheduled
+    pub started: bool,
+}
+
+/// Index of a task in the simulated taskset.
+pub type TaskId = usize;
+
+/// Kind of a simulation event.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// A job is released
+    Release,
+    /// A job is scheduled for the first time
+    Start,
+    /// The running job is descheduled before completing
+    Preempt,
+    /// A previously preempted job is scheduled again
+    Resume,
+    /// A job completes its execution
+    Complete,
+    /// A job reaches its deadline before completing, and is aborted
+    Miss,
+}
+
+/// Receiver of the events happening during a simulation.
+pub trait Observer {
+    fn event(&mut self, time: Time, task: TaskId, kind: EventKind);
+
+    /// Whether the simulation should end early. It is checked after all the
+    /// releases and deadline misses of each instant have been notified.
+    fn should_stop(&self) -> bool {
+        false
+    }
+}
+
+/// Ordered sequence of the events of a simulation.
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    events: Vec<(Time, TaskId, EventKind)>,
+}
+
+/// Scheduling policy used by the simulator.
+pub trait Scheduler {
+    /// Index in `jobs` of the job to execute at time `now`. The simulator
+    /// never calls this function with an empty `jobs` slice.
+    fn select(&self, now: Time, taskset: &[RTTask], jobs: &[Job]) -> usize;
+
+    /// Time, after `now`, at which the scheduling decision must be taken
+    /// again even if no job is released or completed, given that the job at
+    /// index `running` has been selected. Values not after `now` are ignored.
+    fn next_preemption(&self, _now: Time, _taskset: &[RTTask], _jobs: &[Job], _running: usize) -> Option<Time> {
+        None
+    }
+}
+
+/// Earliest Deadline First. Ties are broken in favour of the lower task index.
+pub struct EdfScheduler;
+
+/// Rate Monotonic. Ties are broken in favour of the lower task index.
+pub struct RmScheduler;
+
+/// Least Laxity First, where the laxity of a job is the time left until its
+/// deadline minus its remaining execution time.
+///
+/// The laxity of waiting jobs decreases over time, while the one of the
+/// running job is constant, so the decision is revisited when a waiting job
+/// would become less lax than the running one. Ties are broken in favour of
+/// the earlier deadline and then of the lower task index. Jobs whose laxities
+/// tie would preempt each other continuously: the `quantum` is the minimum
+/// time between two such decisions, which bounds the number of preemptions.
+pub struct LlfScheduler {
+    pub quantum: Time,
+}
+
+/// Outcome of a simulation.
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    /// Number of jobs, per task, whose deadline falls within the horizon
+    pub jobs: Vec<u64>,
+    /// Number of deadline misses per task
+    pub misses: Vec<u64>,
+    /// Total time the processor spent executing jobs
+    pub busy_time: Time,
+}
+
+impl Job {
+    pub fn new(task: usize, release: Time, deadline: Time, remaining: Time, started: bool) -> Self {
+        Self { task, release, deadline, remaining, started }
+    }
+}
+
+impl SimulationResult {
+    pub fn new(jobs: Vec<u64>, misses: Vec<u64>, busy_time: Time) -> Self {
+        Self { jobs, misses, busy_time }
+    }
+}
+
+impl Scheduler for EdfScheduler {
+    fn select(&self, _now: Time, _taskset: &[RTTask], jobs: &[Job]) -> usize {
+        (0..jobs.len())
+            .min_by_key(|&j| (jobs[j].deadline, jobs[j].task, jobs[j].release))
+            .unwrap()
+    }
+}
+
+impl Scheduler for RmScheduler {
+    fn select(&self, _now: Time, taskset: &[RTTask], jobs: &[Job]) -> usize {
+        (0..jobs.len())
+            .min_by_key(|&j| (taskset[jobs[j].task].period, jobs[j].task, jobs[j].release))
+            .unwrap()
+    }
+}
+
+impl LlfScheduler {
+    pub fn new(quantum: Time) -> Self {
+        Self { quantum }
+    }
+
+    fn laxity(now: Time, job: &Job) -> Time {
+        job.deadline - now - job.remaining
+    }
+}
+
+impl Scheduler for LlfScheduler {
+    fn select(&self, now: Time, _taskset: &[RTTask], jobs: &[Job]) -> usize {
+        (0..jobs.len())
+            .min_by_key(|&j| (Self::laxity(now, &jobs[j]), jobs[j].deadline, jobs[j].task))
+            .unwrap()
+    }
+
+    fn next_preemption(&self, now: Time, _taskset: &[RTTask], jobs: &[Job], running: usize) -> Option<Time> {
+        let running_laxity = Self::laxity(now, &jobs[running]);
+
+        (0..jobs.len())
+            .filter(|&j| j != running)
+            .map(|j| Self::laxity(now, &jobs[j]) - running_laxity)
+            .min()
+            .map(|delay| now + Time::max(delay, self.quantum))
+    }
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Simulate the taskset in the interval [0, horizon), recording its events.
+    pub fn record(taskset: &[RTTask], scheduler: &impl Scheduler, horizon: Time) -> Self {
+        let mut timeline = Self::new();
+        simulate_with(taskset, scheduler, horizon, &mut timeline);
+        timeline
+    }
+
+    /// All the events, sorted by time.
+    pub fn events(&self) -> &[(Time, TaskId, EventKind)] {
+        &self.events
+    }
+
+    pub fn task_events(&self, task: TaskId) -> impl Iterator<Item = &(Time, TaskId, EventKind)> {
+        self.events.iter().filter(move |(_, id, _)| *id == task)
+    }
+
+    pub fn events_of_kind(&self, kind: EventKind) -> impl Iterator<Item = &(Time, TaskId, EventKind)> {
+        self.events.iter().filter(move |(_, _, event)| *event == kind)
+    }
+
+    /// Execution intervals `(task, start, end)` of the jobs, sorted by time.
+    pub fn executions(&self) -> Vec<(TaskId, Time, Time)> {
+        let mut executions = Vec::new();
+        let mut running: Option<(TaskId, Time)> = None;
+        for &(time, task, kind) in &self.events {
+            match kind {
+                EventKind::Start | EventKind::Resume => {
+                    if let Some((running_task, start)) = running.take() {
+                        executions.push((running_task, start, time));
+                    }
+
+                    running = Some((task, time));
+                },
+                EventKind::Preempt | EventKind::Complete | EventKind::Miss => {
+                    if let Some((running_task, start)) = running
+                        && running_task == task
+                    {
+                        executions.push((task, start, time));
+                        running = None;
+                    }
+                },
+                EventKind::Release => {},
+            }
+        }
+
+        executions
+    }
+
+    /// Export the timeline in the Chrome Tracing JSON format, which can be
+    /// loaded in `chrome://tracing` or Perfetto. Each task is a thread, its
+    /// executions are duration events, and releases and deadline misses are
+    /// instant events. Timestamps are in microseconds, as per the format.
+    pub fn to_chrome_trace(&self) -> String {
+        let tasks = self.events.iter()
+            .map(|(_, task, _)| task + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut trace_events: Vec<String> = (0..tasks)
+            .map(|task| format!(
+                r#"{{"name":"thread_name","ph":"M","pid":0,"tid":{task},"args":{{"name":"Task {task}"}}}}"#
+            ))
+            .collect();
+
+        trace_events.extend(self.events.iter()
+            .filter(|(_, _, kind)| matches!(kind, EventKind::Release | EventKind::Miss))
+            .map(|(time, task, kind)| format!(
+                r#"{{"name":"{kind:?}","ph":"i","s":"t","pid":0,"tid":{task},"ts":{}}}"#,
+                time.as_micros()
+            )));
+
+        trace_events.extend(self.executions().into_iter()
+            .map(|(task, start, end)| format!(
+                r#"{{"name":"Task {task}","ph":"X","pid":0,"tid":{task},"ts":{},"dur":{}}}"#,
+                start.as_micros(), (end - start).as_micros()
+            )));
+
+        format!(r#"{{"traceEvents":[{}],"displayTimeUnit":"ns"}}"#, trace_events.join(","))
+    }
+}
+
+impl Observer for Timeline {
+    fn event(&mut self, time: Time, task: TaskId, kind: EventKind) {
+        self.events.push((time, task, kind));
+    }
+}
+
+/// Discards all events.
+impl Observer for () {
+    fn event(&mut self, _time: Time, _task: TaskId, _kind: EventKind) { }
+}
+
+/// Stops the simulation at the first deadline miss.
+struct FirstMiss {
+    miss: Option<(Time, TaskId)>,
+}
+
+impl Observer for FirstMiss {
+    fn event(&mut self, time: Time, task: TaskId, kind: EventKind) {
+        if kind == EventKind::Miss && self.miss.is_none_or(|miss| (time, task) < miss) {
+            self.miss = Some((time, task));
+        }
+    }
+
+    fn should_stop(&self) -> bool {
+        self.miss.is_some()
+    }
+}
+
+/// Simulate the taskset in the interval [0, horizon).
+///
+/// Panics if any task has a non-positive period.
+pub fn simulate(taskset: &[RTTask], scheduler: &impl Scheduler, horizon: Time) -> SimulationResult {
+    simulate_with(taskset, scheduler, horizon, &mut ())
+}
+
+/// Simulate the taskset in the interval [0, horizon) until the first deadline
+/// miss, returning its time and task. If several tasks miss a deadline at the
+/// same time, the lowest task index is returned. Returns `None` if no deadline
+/// is missed within the horizon.
+///
+/// Panics if any task has a non-positive period.
+pub fn simulate_until_miss(taskset: &[RTTask], scheduler: &impl Scheduler, horizon: Time) -> Option<(Time, TaskId)> {
+    let mut first_miss = FirstMiss { miss: None };
+    simulate_with(taskset, scheduler, horizon, &mut first_miss);
+    first_miss.miss
+}
+
+/// Simulate the taskset in the interval [0, horizon), notifying the observer
+/// of every event.
+///
+/// Panics if any task has a non-positive period.
+pub fn simulate_with(
+    taskset: &[RTTask],
+    scheduler: &impl Scheduler,
+    horizon: Time,
+    observer: &mut impl Observer,
+) -> SimulationResult {
+    assert!(taskset.iter().all(|task| task.period > Time::zero()),
+        "Simulated tasks must have a positive period");
+
+    let mut result = SimulationResult::new(vec![0; taskset.len()], vec![0; taskset.len()], Time::zero());
+
+    let mut now = Time::zero();
+    let mut next_release = vec![Time::zero(); taskset.len()];
+    let mut jobs: Vec<Job> = Vec::new();
+    let mut current: Option<(TaskId, Time)> = None;
+
+    loop {
+        for (i, task) in taskset.iter().enumerate() {
+            while next_release[i] <= now && next_release[i] < horizon {
+                let job = Job::new(i, next_release[i], next_release[i] + task.deadline, task.wcet, false);
+
+                observer.event(now, i, EventKind::Release);
+
+                if job.deadline <= horizon {
+                    result.jobs[i] += 1;
+                }
+
+                jobs.push(job);
+                next_release[i] = next_release[i] + task.period;
+            }
+        }
+
+        jobs.retain(|job| {
+            let missed = job.deadline <= now;
+            if missed {
+                result.misses[job.task] += 1;
+                observer.event(now, job.task, EventKind::Miss);
+
+                if current == Some((job.task, job.release)) {
+                    current = None;
+                }
+            }
+
+            !missed
+        });
+
+        if now >= horizon || observer.should_stop() {
+            break;
+        }
+
+        let next_event = next_release.iter()
+            .chain(jobs.iter().map(|job| &job.deadline))
+            .copied()
+            .fold(horizon, Time::min);
+
+        if jobs.is_empty() {
+            now = next_event;
+            continue;
+        }
+
+        let running = scheduler.select(now, taskset, &jobs);
+        let next_event = match scheduler.next_preemption(now, taskset, &jobs, running) {
+            Some(preemption) if preemption > now => Time::min(next_event, preemption),
+            _ => next_event,
+        };
+
+        let job = &mut jobs[running];
+        if current != Some((job.task, job.release)) {
+            if let Some((task, _)) = current {
+                observer.event(now, task, EventKind::Preempt);
+            }
+
+            let kind = if job.started { EventKind::Resume } else { EventKind::Start };
+            observer.event(now, job.task, kind);
+            job.started = true;
+            current = Some((job.task, job.release));
+        }
+
+        let completion = now + jobs[running].remaining;
+        if completion <= next_event {
+            result.busy_time = result.busy_time + jobs[running].remaining;
+            observer.event(completion, jobs[running].task, EventKind::Complete);
+            jobs.swap_remove(running);
+            current = None;
+            now = completion;
+        } else {
+            result.busy_time = result.busy_time + (next_event - now);
+            jobs[running].remaining = jobs[running].remaining - (next_event - now);
+            now = next_event;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use crate::utils::RTUtils;
+
+    fn ns(value: f64) -> Time {
+        Time::nanos(value)
+    }
+
+    #[test]
+    fn simulate_overloaded_taskset() {
+        // every job of the second task ties with one of the first at its
+        // deadline, loses the tie and is aborted without executing
+        let taskset = [RTTask::new_ns(2, 2, 2), RTTask::new_ns(1, 4, 4)];
+        let result = simulate(&taskset, &EdfScheduler, ns(8.0));
+
+        assert_eq!(result.jobs, vec![4, 2]);
+        assert_eq!(result.misses, vec![0, 2]);
+        assert_eq!(result.busy_time, ns(8.0));
+    }
+
+    #[test]
+    fn simulate_feasible_taskset() {
+        let taskset = [RTTask::new_ns(1, 2, 2), RTTask::new_ns(2, 4, 4)];
+        let result = simulate(&taskset, &EdfScheduler, ns(8.0));
+
+        assert_eq!(result.jobs, vec![4, 2]);
+        assert_eq!(result.misses, vec![0, 0]);
+        assert_eq!(result.busy_time, ns(8.0));
+    }
+
+    #[test]
+    fn llf_meets_all_deadlines_up_to_full_utilization() {
+        let tasksets = random_tasksets(128, 2000).into_iter()
+            .map(|taskset| taskset.iter()
+                .map(|task| RTTask::new(task.wcet, task.period, task.period))
+                .collect::<Vec<_>>())
+            .filter(|taskset| RTUtils::total_utilization(taskset) <= 1.0)
+            .chain([
+                vec![RTTask::new_ns(2, 4, 4), RTTask::new_ns(2, 4, 4)],
+                vec![RTTask::new_ns(1, 2, 2), RTTask::new_ns(1, 3, 3), RTTask::new_ns(1, 6, 6)],
+            ]);
+
+        let scheduler = LlfScheduler::new(ns(1.0));
+        for taskset in tasksets {
+            let result = simulate(&taskset, &scheduler, RTUtils::hyperperiod(&taskset));
+            assert!(result.misses.iter().all(|&misses| misses == 0), "{taskset:?}");
+        }
+    }
+
+    #[test]
+    fn timeline_of_a_two_task_run() {
+        use EventKind::*;
+
+        let taskset = [RTTask::new_ns(1, 3, 3), RTTask::new_ns(3, 6, 6)];
+        let timeline = Timeline::record(&taskset, &RmScheduler, ns(6.0));
+
+        assert_eq!(timeline.events(), &[
+            (ns(0.0), 0, Release),
+            (ns(0.0), 1, Release),
+            (ns(0.0), 0, Start),
+            (ns(1.0), 0, Complete),
+            (ns(1.0), 1, Start),
+            (ns(3.0), 0, Release),
+            (ns(3.0), 1, Preempt),
+            (ns(3.0), 0, Start),
+            (ns(4.0), 0, Complete),
+            (ns(4.0), 1, Resume),
+            (ns(5.0), 1, Complete),
+        ]);
+
+        assert_eq!(timeline.executions(), vec![
+            (0, ns(0.0), ns(1.0)),
+            (1, ns(1.0), ns(3.0)),
+            (0, ns(3.0), ns(4.0)),
+            (1, ns(4.0), ns(5.0)),
+        ]);
+
+        assert_eq!(timeline.task_events(0).count(), 6);
+        assert_eq!(timeline.events_of_kind(Preempt).collect::<Vec<_>>(), vec![&(ns(3.0), 1, Preempt)]);
+        assert_eq!(timeline.events_of_kind(Miss).count(), 0);
+    }
+
+    #[test]
+    fn chrome_trace_of_a_two_task_run() {
+        let taskset = [RTTask::new_ns(1, 3, 3), RTTask::new_ns(3, 6, 6)];
+        let trace = Timeline::record(&taskset, &RmScheduler, ns(6.0)).to_chrome_trace();
+
+        assert!(is_valid_json(&trace), "{trace}");
+        assert!(trace.contains(r#""tid":1,"args":{"name":"Task 1"}"#));
+        assert!(trace.contains(r#"{"name":"Release","ph":"i","s":"t","pid":0,"tid":0,"ts":0.003}"#));
+        assert!(trace.contains(r#"{"name":"Task 1","ph":"X","pid":0,"tid":1,"ts":0.001,"dur":0.002}"#));
+        assert_eq!(trace.matches(r#""ph":"X""#).count(), 4);
+
+        assert!(is_valid_json(&Timeline::new().to_chrome_trace()));
+        assert!(!is_valid_json(r#"{"traceEvents":[}"#));
+    }
+
+    #[test]
+    fn first_deadline_miss() {
+        // Under RM the second task runs in [2, 4), is preempted at 4 and
+        // misses its deadline at 6 with one unit left.
+        let taskset = [RTTask::new_ns(2, 4, 4), RTTask::new_ns(3, 6, 6), RTTask::new_ns(1, 100, 100)];
+        assert_eq!(simulate_until_miss(&taskset, &RmScheduler, ns(100.0)), Some((ns(6.0), 1)));
+        assert_eq!(simulate_until_miss(&taskset, &RmScheduler, ns(5.0)), None);
+
+        // At full utilization EDF meets every deadline. With the third task
+        // the demand first exceeds the time at 108, where it is 54 + 54 + 1.
+        assert_eq!(simulate_until_miss(&taskset[..2], &EdfScheduler, ns(120.0)), None);
+        assert_eq!(simulate_until_miss(&taskset, &EdfScheduler, ns(120.0)), Some((ns(108.0), 1)));
+
+        // The second and third task miss their deadlines at the same time
+        let ties = [RTTask::new_ns(2, 2, 2), RTTask::new_ns(2, 2, 2), RTTask::new_ns(1, 2, 2)];
+        assert_eq!(simulate_until_miss(&ties, &RmScheduler, ns(10.0)), Some((ns(2.0), 1)));
+    }
+}
@@ -0,0 +1,169 @@
+//! SuspendingTask struct.
+//!
+//! This module defines the `SuspendingTask` struct, which describes a
+//! real-time task that may self-suspend, e.g. while waiting for I/O or for an
+//! accelerator, for at most a given total time per job. A suspending job does
+//! not use the processor while suspended, but its completion is delayed, and
+//! it may execute later than a non-suspending one, which increases the
+//! interference on lower priority tasks.
+
+use crate::prelude::*;
+use crate::utils::RTUtils;
+
+pub mod prelude {
+    pub use super::{
+        SuspendingTask,
+    };
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SuspendingTask {
+    pub task: RTTask,
+    /// Maximum total suspension time of a job
+    pub suspension: Time,
+}
+
+impl SuspendingTask {
+    pub fn new(task: RTTask, suspension: Time) -> Self {
+        Self { task, suspension }
+    }
+}
+
+impl RTUtils {
+    /// Response-time analysis for self-suspending tasks under fixed
+    /// priorities, with tasks sorted by decreasing priority and constrained
+    /// deadlines.
+    ///
+    /// The suspension of the analysed task is accounted as execution time,
+    /// while each higher priority task interferes as a task with release
+    /// jitter `J_j = R_j - C_j`, giving the recurrence `R_i = C_i + S_i +
+    /// sum_j ceil((R_i + R_j - C_j) / T_j) * C_j`. Returns `None` for tasks
+    /// whose response time exceeds their deadline, and for all the following
+    /// ones.
+    ///
+    /// The jitter `J_j = S_j`, often used in the literature, is not sound:
+    /// a suspension can defer the execution of a job by more than `S_j`, up to
+    /// its response time, as shown by Chen et al. ("Many suspensions, many
+    /// problems", 2016). The bound used here is the corrected one.
+    pub fn suspending_response_time_fp(taskset: &[SuspendingTask]) -> Vec<Option<Time>> {
+        let mut responses: Vec<Option<Time>> = Vec::with_capacity(taskset.len());
+        for (i, task) in taskset.iter().enumerate() {
+            let Some(jitters) = responses.iter().zip(taskset)
+                .map(|(response, higher)| Some((*response)? - higher.task.wcet))
+                .collect::<Option<Vec<Time>>>()
+            else {
+                responses.push(None);
+                continue;
+            };
+
+            let base = task.task.wcet + task.suspension;
+            let mut response = base;
+            let response = loop {
+                let next = base + taskset[..i].iter().zip(&jitters)
+                    .map(|(higher, &jitter)| higher.task.interference(response + jitter))
+                    .sum();
+
+                if next > task.task.deadline {
+                    break None;
+                }
+
+                if next == response {
+                    break Some(next);
+                }
+
+                response = next;
+            };
+
+            responses.push(response);
+        }
+
+        responses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn ns(value: f64) -> Time {
+        Time::nanos(value)
+    }
+
+    fn suspending(wcet: u64, suspension: u64, deadline: u64, period: u64) -> SuspendingTask {
+        SuspendingTask::new(RTTask::new_ns(wcet, deadline, period), ns(suspension as f64))
+    }
+
+    #[test]
+    fn suspension_aware_response_times() {
+        // Hand-computed: R1 = 1 + 1; R2 = 3 + ceil((4 + 1) / 5) * 1;
+        // R3 = 2 + ceil((6 + 1) / 5) * 1 + ceil((6 + 2) / 10) * 2.
+        let taskset = [
+            suspending(1, 1, 5, 5),
+            suspending(2, 1, 10, 10),
+            suspending(2, 0, 20, 20),
+        ];
+        assert_eq!(RTUtils::suspending_response_time_fp(&taskset),
+            vec![Some(ns(2.0)), Some(ns(4.0)), Some(ns(6.0))]);
+
+        // Never below the response times without suspensions.
+        let plain: Vec<_> = taskset.iter().map(|task| task.task.clone()).collect();
+        let plain = RTUtils::response_time_fp(&plain);
+        assert_eq!(plain, vec![Some(ns(1.0)), Some(ns(3.0)), Some(ns(5.0))]);
+        for (suspending, plain) in RTUtils::suspending_response_time_fp(&taskset).into_iter().zip(plain) {
+            assert!(suspending.unwrap() >= plain.unwrap());
+        }
+    }
+
+    #[test]
+    fn suspensions_never_reduce_the_response_times() {
+        let mut rng = crate::generation::Rng::new(151);
+        for taskset in random_tasksets(151, 2000) {
+            let mut taskset: Vec<RTTask> = taskset.into_iter()
+                .map(|task| RTTask { deadline: Time::min(task.deadline, task.period), ..task })
+                .collect();
+            taskset.sort_by_key(|task| task.deadline);
+
+            let suspending: Vec<_> = taskset.iter()
+                .map(|task| {
+                    let suspension = uniform(&mut rng, 0, task.wcet.as_nanos() as u64);
+                    SuspendingTask::new(task.clone(), ns(suspension as f64))
+                })
+                .collect();
+
+            let plain = RTUtils::response_time_fp(&taskset);
+            for (suspending, plain) in RTUtils::suspending_response_time_fp(&suspending).into_iter().zip(plain) {
+                if let Some(suspending) = suspending {
+                    assert!(plain.is_some_and(|plain| suspending >= plain), "{taskset:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn jitter_is_the_higher_priority_response_time() {
+        // The second task has response time 4 but suspends for 1 only. With
+        // J_2 = S_2 = 1 the recurrence of the third task would stop at
+        // 1 + 2 + 1 = 4, while the jitter R_2 - C_2 = 3 gives 1 + 3 + 2 = 6.
+        let taskset = [
+            suspending(1, 0, 2, 2),
+            suspending(1, 1, 5, 5),
+            suspending(1, 0, 20, 20),
+        ];
+
+        assert_eq!(RTUtils::suspending_response_time_fp(&taskset),
+            vec![Some(ns(1.0)), Some(ns(4.0)), Some(ns(6.0))]);
+    }
+
+    #[test]
+    fn suspension_miss_propagates() {
+        let taskset = [
+            suspending(1, 1, 5, 5),
+            suspending(2, 1, 3, 10),
+            suspending(1, 0, 20, 20),
+        ];
+
+        assert_eq!(RTUtils::suspending_response_time_fp(&taskset), vec![Some(ns(2.0)), None, None]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Helpers shared by the unit tests.
+
+use crate::prelude::*;
+use crate::generation::Rng;
+
+/// Uniformly distributed integer in [lo, hi].
+pub(crate) fn uniform(rng: &mut Rng, lo: u64, hi: u64) -> u64 {
+    lo + rng.next_u64() % (hi - lo + 1)
+}
+
+/// Random taskset of `n` tasks with integer parameters, periods among small
+/// values with a short hyperperiod, WCETs up to the period and arbitrary
+/// deadlines in [WCET, 2 * period].
+pub(crate) fn random_taskset(rng: &mut Rng, n: usize) -> Vec<RTTask> {
+    const PERIODS: [u64; 8] = [2, 3, 4, 5, 6, 8, 10, 12];
+
+    (0..n)
+        .map(|_| {
+            let period = PERIODS[uniform(rng, 0, PERIODS.len() as u64 - 1) as usize];
+            let wcet = uniform(rng, 1, period);
+            let deadline = uniform(rng, wcet, 2 * period);
+
+            RTTask::new_ns(wcet, deadline, period)
+        })
+        .collect()
+}
+
+/// Random tasksets of one to four tasks, see `random_taskset`.
+pub(crate) fn random_tasksets(seed: u64, count: usize) -> Vec<Vec<RTTask>> {
+    let mut rng = Rng::new(seed);
+
+    (0..count)
+        .map(|_| {
+            let n = uniform(&mut rng, 1, 4) as usize;
+            random_taskset(&mut rng, n)
+        })
+        .collect()
+}
+
+/// Whether the string is a single, well-formed JSON value.
+pub(crate) fn is_valid_json(json: &str) -> bool {
+    let mut chars = json.chars().peekable();
+
+    json_value(&mut chars) && { skip_whitespace(&mut chars); chars.next().is_none() }
+}
+
+type JsonChars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+fn skip_whitespace(chars: &mut JsonChars) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() { }
+}
+
+fn json_value(chars: &mut JsonChars) -> bool {
+    skip_whitespace(chars);
+    match chars.peek() {
+        Some('{') => json_sequence(chars, '}', |chars| {
+            skip_whitespace(chars);
+            json_string(chars) && { skip_whitespace(chars); chars.next() == Some(':') } && json_value(chars)
+        }),
+        Some('[') => json_sequence(chars, ']', json_value),
+        Some('"') => json_string(chars),
+        Some(c) if *c == '-' || c.is_ascii_digit() => {
+            let number: String = std::iter::from_fn(|| chars.next_if(|c| "+-.eE".contains(*c) || c.is_ascii_digit()))
+                .collect();
+            number.parse::<f64>().is_ok()
+        },
+        Some(&first) => ["true", "false", "null"].iter()
+            .find(|literal| literal.starts_with(first))
+            .is_some_and(|literal| literal.chars().all(|c| chars.next_if_eq(&c).is_some())),
+        None => false,
+    }
+}
+
+fn json_sequence(chars: &mut JsonChars, close: char, item: impl Fn(&mut JsonChars) -> bool) -> bool {
+    chars.next();
+    skip_whitespace(chars);
+    if chars.next_if_eq(&close).is_some() {
+        return true;
+    }
+
+    loop {
+        if !item(chars) {
+            return false;
+        }
+
+        skip_whitespace(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some(c) => return c == close,
+            None => return false,
+        }
+    }
+}
+
+fn json_string(chars: &mut JsonChars) -> bool {
+    if chars.next() != Some('"') {
+        return false;
+    }
+
+    loop {
+        match chars.next() {
+            Some('"') => return true,
+            Some('\\') => { chars.next(); },
+            Some(_) => {},
+            None => return false,
+        }
+    }
+}
+
+/// Minimal self-describing serde data model, to test serialization round
+/// trips without depending on a data format crate.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Str(String),
+    F64(f64),
+    Map(Vec<(String, Value)>),
+}
+
+type SerdeError = serde::de::value::Error;
+
+impl Value {
+    pub(crate) fn from<T: serde::Serialize + ?Sized>(value: &T) -> Value {
+        value.serialize(ValueSerializer).unwrap()
+    }
+
+    pub(crate) fn deserialize<'de, T: serde::Deserialize<'de>>(self) -> Result<T, SerdeError> {
+        T::deserialize(serde::de::IntoDeserializer::<SerdeError>::into_deserializer(self))
+    }
+}
+
+pub(crate) struct ValueSerializer;
+
+/// Fields of a struct being serialized.
+pub(crate) struct ValueStruct(Vec<(String, Value)>);
+
+impl serde::ser::SerializeStruct for ValueStruct {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        self.0.push((key.to_string(), value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(Value::Map(self.0))
+    }
+}
+
+fn unsupported<T>() -> Result<T, SerdeError> {
+    Err(serde::ser::Error::custom("unsupported by the test data model"))
+}
+
+impl serde::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerdeError;
+    type SerializeSeq = serde::ser::Impossible<Value, SerdeError>;
+    type SerializeTuple = serde::ser::Impossible<Value, SerdeError>;
+    type SerializeTupleStruct = serde::ser::Impossible<Value, SerdeError>;
+    type SerializeTupleVariant = serde::ser::Impossible<Value, SerdeError>;
+    type SerializeMap = serde::ser::Impossible<Value, SerdeError>;
+    type SerializeStruct = ValueStruct;
+    type SerializeStructVariant = serde::ser::Impossible<Value, SerdeError>;
+
+    fn serialize_str(self, v: &str) -> Result<Value, SerdeError> { Ok(Value::Str(v.to_string())) }
+    fn serialize_f64(self, v: f64) -> Result<Value, SerdeError> { Ok(Value::F64(v)) }
+    fn serialize_f32(self, v: f32) -> Result<Value, SerdeError> { Ok(Value::F64(v as f64)) }
+    fn serialize_u64(self, v: u64) -> Result<Value, SerdeError> { Ok(Value::F64(v as f64)) }
+    fn serialize_i64(self, v: i64) -> Result<Value, SerdeError> { Ok(Value::F64(v as f64)) }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Value, SerdeError> {
+        Ok(Value::Str(variant.to_string()))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<ValueStruct, SerdeError> {
+        Ok(ValueStruct(Vec::with_capacity(len)))
+    }
+
+    fn serialize_newtype_struct<T: serde::Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Value, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_i8(self, _v: i8) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_i16(self, _v: i16) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_i32(self, _v: i32) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_u8(self, _v: u8) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_u16(self, _v: u16) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_u32(self, _v: u32) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_char(self, _v: char) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_none(self) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_unit(self) -> Result<Value, SerdeError> { unsupported() }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerdeError> { unsupported() }
+
+    fn serialize_some<T: serde::Serialize + ?Sized>(self, _value: &T) -> Result<Value, SerdeError> {
+        unsupported()
+    }
+
+    fn serialize_newtype_variant<T: serde::Serialize + ?Sized>(
+        self, _name: &'static str, _index: u32, _variant: &'static str, _value: &T,
+    ) -> Result<Value, SerdeError> {
+        unsupported()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> { unsupported() }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> { unsupported() }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> { unsupported() }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        unsupported()
+    }
+
+    fn serialize_tuple_variant(
+        self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        unsupported()
+    }
+
+    fn serialize_struct_variant(
+        self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        unsupported()
+    }
+}
+
+impl<'de> serde::de::IntoDeserializer<'de, SerdeError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for Value {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Value::Str(v) => visitor.visit_string(v),
+            Value::F64(v) => visitor.visit_f64(v),
+            Value::Map(fields) => visitor.visit_map(serde::de::value::MapDeserializer::new(fields.into_iter())),
+        }
+    }
+
+    fn deserialize_enum<V: serde::de::Visitor<'de>>(
+        self, _name: &'static str, _variants: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self {
+            Value::Str(variant) => visitor.visit_enum(serde::de::IntoDeserializer::into_deserializer(variant)),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
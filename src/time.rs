@@ -18,27 +18,93 @@ pub mod prelude {
     pub use super::{
         Time,
         Time2,
+        TimeUnit,
+        Seconds,
+        Nanos,
+        TimeSpan,
+        Interval,
     };
 }
 
-#[derive(Debug)]
 #[derive(Clone, Copy)]
 pub struct Time {
     pub value_ns: f64,
 }
 
-#[derive(Debug)]
 #[derive(Clone, Copy)]
 pub struct Time2 {
     pub value_ns_2: f64,
 }
 
+/// Non-negative `Time`, describing durations such as WCETs and periods, as
+/// opposed to the signed `Time` which can also describe differences between
+/// times. It is obtained from a `Time` through `TryFrom`, which rejects
+/// negative and NaN values, and converts back into it with `From`.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "Time", into = "Time")]
+pub struct TimeSpan(Time);
+
+/// Time interval [start, end).
+#[derive(Debug, Clone, Copy)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Interval {
+    pub start: Time,
+    pub end: Time,
+}
+
+/// Bare number of seconds, for interoperation with code using plain `f64`s.
+/// The unit is explicit at the conversion site, e.g. `Time::from(Seconds(0.5))`.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Seconds(pub f64);
+
+/// Bare number of nanoseconds, see `Seconds`.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Nanos(pub f64);
+
+/// Units of measure for time values.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeUnit {
+    Ns,
+    Us,
+    Ms,
+    S,
+}
+
 // =============================================================================
 
+impl TimeUnit {
+    /// Nanoseconds in one unit
+    pub fn in_nanos(&self) -> f64 {
+        match self {
+            TimeUnit::Ns => 1.0,
+            TimeUnit::Us => Time::MICRO_TO_NANO,
+            TimeUnit::Ms => Time::MILLI_TO_NANO,
+            TimeUnit::S => Time::SECS_TO_NANO,
+        }
+    }
+
+    /// Suffix printed after values in this unit
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            TimeUnit::Ns => "ns",
+            TimeUnit::Us => "us",
+            TimeUnit::Ms => "ms",
+            TimeUnit::S => "s",
+        }
+    }
+}
+
 impl Time {
     pub const MICRO_TO_NANO: f64 = 1000.0;
-    pub const MILLI_TO_NANO: f64 = 1000_000.0;
-    pub const SECS_TO_NANO: f64 = 1000_000_000.0;
+    pub const MILLI_TO_NANO: f64 = 1_000_000.0;
+    pub const SECS_TO_NANO: f64 = 1_000_000_000.0;
 
     pub fn zero() -> Self {
         Self { value_ns: 0.0 }
@@ -64,6 +130,17 @@ impl Time {
         Self { value_ns: time_s * Self::SECS_TO_NANO }
     }
 
+    /// Time of the given value in the given unit
+    pub fn of(value: f64, unit: TimeUnit) -> Self {
+        Self { value_ns: value * unit.in_nanos() }
+    }
+
+    /// Time taken by the given number of cycles of a clock with frequency
+    /// `hz`.
+    pub fn from_cycles(cycles: u64, hz: f64) -> Self {
+        Self { value_ns: cycles as f64 / hz * Self::SECS_TO_NANO }
+    }
+
     pub fn as_nanos(&self) -> f64 {
         self.value_ns
     }
@@ -80,6 +157,69 @@ impl Time {
         self.value_ns / Self::SECS_TO_NANO
     }
 
+    /// Value of the time in the given unit
+    pub fn as_unit(&self, unit: TimeUnit) -> f64 {
+        self.value_ns / unit.in_nanos()
+    }
+
+    /// Number of cycles of a clock with frequency `hz` elapsed in this time.
+    pub fn to_cycles(&self, hz: f64) -> f64 {
+        self.value_ns / Self::SECS_TO_NANO * hz
+    }
+
+    /// Nanoseconds, rounded down, as an unsigned integer. Returns `None` if
+    /// the time is not finite or does not fit in an `u64`.
+    pub fn try_as_u64_nanos(&self) -> Option<u64> {
+        let value = self.value_ns.floor();
+        if value.is_finite() && value >= 0.0 && value < u64::MAX as f64 {
+            Some(value as u64)
+        } else {
+            None
+        }
+    }
+
+    /// Nanoseconds, rounded down, as a signed integer. Returns `None` if the
+    /// time is not finite or does not fit in an `i64`.
+    pub fn try_as_i64_nanos(&self) -> Option<i64> {
+        let value = self.value_ns.floor();
+        if value.is_finite() && value >= i64::MIN as f64 && value < i64::MAX as f64 {
+            Some(value as i64)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the two times differ by at most `tol`. Unlike `==`, which uses
+    /// a fixed tolerance of half a nanosecond, the tolerance is explicit.
+    pub fn approx_eq(&self, other: Time, tol: Time) -> bool {
+        f64::abs(self.value_ns - other.value_ns) <= tol.value_ns
+    }
+
+    /// Whether the time is within `tol` of an integer number of nanoseconds.
+    pub fn is_integer_ns(&self, tol: Time) -> bool {
+        f64::abs(self.value_ns - self.value_ns.round()) <= tol.value_ns
+    }
+
+    /// Nanoseconds, rounded to the nearest integer. Values out of the range
+    /// of `i64` saturate, and NaN is converted to zero.
+    pub fn nearest_integer_ns(&self) -> i64 {
+        self.value_ns.round() as i64
+    }
+
+    /// Format the time in the given unit, with three decimal digits, right
+    /// aligned in a field of the given width. Unlike `Display`, the unit is
+    /// fixed, so that values can be aligned in columns. As in `Display`, NaN,
+    /// inf and -inf are printed without unit.
+    pub fn format_fixed(&self, width: usize, unit: TimeUnit) -> String {
+        let value = if self.value_ns.is_finite() {
+            format!("{:.3}{}", self.as_unit(unit), unit.suffix())
+        } else {
+            format!("{}", self.value_ns)
+        };
+
+        format!("{value:>width$}")
+    }
+
     pub fn floor(self) -> Self {
         Self { value_ns: f64::floor(self.value_ns) }
     }
@@ -91,6 +231,38 @@ impl Time {
     pub fn round(self) -> Self {
         Self { value_ns: f64::round(self.value_ns) }
     }
+
+    /// Euclidean remainder, in [0, rhs) for a positive `rhs`. Unlike `%`, the
+    /// operands are not rounded down to integer nanoseconds.
+    pub fn rem_euclid(self, rhs: Self) -> Self {
+        Self { value_ns: f64::rem_euclid(self.value_ns, rhs.value_ns) }
+    }
+
+    /// Time halfway between the two, computed without overflowing.
+    pub fn midpoint(self, other: Self) -> Self {
+        Self { value_ns: f64::midpoint(self.value_ns, other.value_ns) }
+    }
+
+    /// Time², same as `self * self`.
+    pub fn squared(self) -> Time2 {
+        self * self
+    }
+
+    /// Raise the time, in nanoseconds, to the given power.
+    ///
+    /// The result has unit *ns^exp*, which cannot be represented by a typed
+    /// struct, thus it is returned as a plain scalar. It depends on the unit
+    /// of measure, e.g. `Time::millis(2.0).powf(0.5)` is not `sqrt(2)`.
+    pub fn powf(self, exp: f64) -> f64 {
+        self.value_ns.powf(exp)
+    }
+}
+
+/// Zero time.
+impl Default for Time {
+    fn default() -> Self {
+        Self::zero()
+    }
 }
 
 impl PartialEq for Time {
@@ -105,8 +277,7 @@ impl Eq for Time { }
 
 impl PartialOrd for Time {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        ordered_float::OrderedFloat(self.value_ns)
-            .partial_cmp(&ordered_float::OrderedFloat(other.value_ns))
+        Some(self.cmp(other))
     }
 }
 
@@ -117,6 +288,33 @@ impl Ord for Time {
     }
 }
 
+/// Assert that two `Time`s differ by at most the given tolerance, see
+/// `Time::approx_eq`. On failure, both values are printed with their `Debug`
+/// representation, optionally followed by a custom message.
+#[macro_export]
+macro_rules! assert_time_eq {
+    ($left:expr, $right:expr, $tol:expr $(,)?) => {
+        match (&$left, &$right, &$tol) {
+            (left, right, tol) => {
+                if !$crate::time::Time::approx_eq(left, *right, *tol) {
+                    panic!("assertion `left ~= right` failed (tolerance {:?})\n  left: {:?}\n right: {:?}",
+                        tol, left, right);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $tol:expr, $($arg:tt)+) => {
+        match (&$left, &$right, &$tol) {
+            (left, right, tol) => {
+                if !$crate::time::Time::approx_eq(left, *right, *tol) {
+                    panic!("assertion `left ~= right` failed (tolerance {:?}): {}\n  left: {:?}\n right: {:?}",
+                        tol, format_args!($($arg)+), left, right);
+                }
+            }
+        }
+    };
+}
+
 impl std::ops::Neg for Time {
     type Output = Time;
 
@@ -183,23 +381,76 @@ impl std::ops::Rem for Time {
 
 impl std::iter::Sum for Time {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Time::zero(), |acc, val| acc + val)
+        Time::nanos(crate::utils::compensated_sum(iter.map(|time| time.value_ns)))
     }
 }
 
-impl std::fmt::Display for Time {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+const MINUTES_TO_NANO: f64 = 60.0 * Time::SECS_TO_NANO;
+const HOURS_TO_NANO: f64 = 60.0 * MINUTES_TO_NANO;
+const DAYS_TO_NANO: f64 = 24.0 * HOURS_TO_NANO;
+
+impl Time {
+    fn fmt_scaled(&self, f: &mut std::fmt::Formatter<'_>, precision: usize) -> std::fmt::Result {
+        // NaN, inf and -inf, without unit
+        if !self.value_ns.is_finite() {
+            return write!(f, "{}", self.value_ns);
+        }
+
+        // units are chosen by magnitude, so that negative times are promoted
+        // too. Below a million milliseconds the unit is at most `ms`, from
+        // there on the time is promoted to minutes, hours or days, and a
+        // million days and more are in scientific notation.
         let milli = self.value_ns / Self::MILLI_TO_NANO;
-        if milli >= 1.0 {
-            return write!(f, "{milli:.3}ms");
+        if milli.abs() >= 1e6 {
+            let days = self.value_ns / DAYS_TO_NANO;
+            if days.abs() >= 1e6 {
+                return write!(f, "{days:.precision$e}d");
+            }
+
+            if days.abs() >= 1.0 {
+                return write!(f, "{days:.precision$}d");
+            }
+
+            let hours = self.value_ns / HOURS_TO_NANO;
+            if hours.abs() >= 1.0 {
+                return write!(f, "{hours:.precision$}h");
+            }
+
+            let minutes = self.value_ns / MINUTES_TO_NANO;
+            return write!(f, "{minutes:.precision$}min");
+        }
+
+        if milli.abs() >= 1.0 {
+            return write!(f, "{milli:.precision$}ms");
         }
 
         let micro = self.value_ns / Self::MICRO_TO_NANO;
-        if micro >= 1.0 {
-            return write!(f, "{micro:.3}us");
+        if micro.abs() >= 1.0 {
+            return write!(f, "{micro:.precision$}us");
         }
 
-        write!(f, "{:.3}ns", self.value_ns)
+        write!(f, "{:.precision$}ns", self.value_ns)
+    }
+}
+
+/// Formats in the largest unit, among `ns`, `us` and `ms`, in which the
+/// magnitude of the value is at least one, with three decimals. Very large
+/// times, from a million milliseconds up, are instead printed in the largest
+/// of `min`, `h` and `d`, and from a million days up in scientific notation,
+/// e.g. `1.234e9d`.
+impl std::fmt::Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_scaled(f, 3)
+    }
+}
+
+/// Formats as `Time(1.000ms)`, with the same unit as `Display`. The number of
+/// decimals defaults to three and can be set with the precision, e.g. `{:.6?}`.
+impl std::fmt::Debug for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Time(")?;
+        self.fmt_scaled(f, f.precision().unwrap_or(3))?;
+        write!(f, ")")
     }
 }
 
@@ -212,34 +463,71 @@ impl serde::Serialize for Time {
     }
 }
 
+/// Deserializes strings as parsed by `FromStr`.
 impl<'de> serde::Deserialize<'de> for Time {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let time_string = String::deserialize(deserializer)?;
-
-        let pieces: Vec<_> = time_string.trim().split_whitespace().collect();
-        if pieces.len() == 1 {
-            let time: f64 = pieces[0].parse()
-                .map_err(|err| serde::de::Error::custom(format!("Invalid time: {err}")))?;
-
-            Ok(Time { value_ns: time })
-        } else if pieces.len() == 2 {
-            let time: f64 = pieces[0].parse()
-                .map_err(|err| serde::de::Error::custom(format!("Invalid time: {err}")))?;
-            let unit = match pieces[1] {
-                "s" => Time::SECS_TO_NANO,
-                "ms" => Time::MILLI_TO_NANO,
-                "us" => Time::MICRO_TO_NANO,
-                "ns" => 1.0,
-                u => { return Err(serde::de::Error::custom(format!("Unknown time unit: {u}"))); }
-            };
-
-            Ok(Time::nanos(time * unit))
-        } else {
-            return Err(serde::de::Error::custom("Parsing error, unknown format"));
-        }
+        deserializer.deserialize_str(TimeVisitor)
+    }
+}
+
+/// Parses strings such as `"5 ms"` or `"5ms"`, with unit `d`, `h`, `min`,
+/// `s`, `ms`, `us` or `ns`, or `"5"` for nanoseconds.
+impl std::str::FromStr for Time {
+    type Err = crate::error::RTError;
+
+    fn from_str(time_string: &str) -> Result<Self, Self::Err> {
+        parse_time(time_string).map_err(crate::error::RTError::InvalidArgument)
+    }
+}
+
+fn parse_time(time_string: &str) -> Result<Time, String> {
+    let pieces: Vec<_> = time_string.split_whitespace().collect();
+    let (value, unit) = match pieces[..] {
+        [value, unit] => (value, unit),
+        [piece] => {
+            let value = piece.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            if value.is_empty() {
+                (piece, "")
+            } else {
+                (value, &piece[value.len()..])
+            }
+        },
+        _ => return Err("Parsing error, unknown format".to_string()),
+    };
+
+    let time: f64 = value.parse()
+        .map_err(|err| format!("Invalid time: {err}"))?;
+    let unit = match unit {
+        "d" => DAYS_TO_NANO,
+        "h" => HOURS_TO_NANO,
+        "min" => MINUTES_TO_NANO,
+        "s" => Time::SECS_TO_NANO,
+        "ms" => Time::MILLI_TO_NANO,
+        "us" => Time::MICRO_TO_NANO,
+        "ns" | "" => 1.0,
+        u => { return Err(format!("Unknown time unit: {u}")); }
+    };
+
+    Ok(Time::nanos(time * unit))
+}
+
+struct TimeVisitor;
+
+impl<'de> serde::de::Visitor<'de> for TimeVisitor {
+    type Value = Time;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "a time, as a string with an optional unit")
+    }
+
+    fn visit_str<E>(self, time_string: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        parse_time(time_string).map_err(E::custom)
     }
 }
 
@@ -248,6 +536,16 @@ impl Time2 {
         Self { value_ns_2: value }
     }
 
+    /// Product of two times, same as `a * b`.
+    pub fn from_product(a: Time, b: Time) -> Self {
+        a * b
+    }
+
+    /// Square of a time, same as `t * t`.
+    pub fn from_time(t: Time) -> Self {
+        t * t
+    }
+
     pub fn value(&self) -> f64 {
         self.value_ns_2
     }
@@ -255,6 +553,29 @@ impl Time2 {
     pub fn sqrt(self) -> Time {
         Time::nanos(self.value_ns_2.sqrt())
     }
+
+    pub fn abs(self) -> Self {
+        Self { value_ns_2: self.value_ns_2.abs() }
+    }
+
+    /// Strictly less than zero
+    pub fn is_negative(&self) -> bool {
+        self.value_ns_2 < 0.0
+    }
+
+    /// Strictly greater than zero
+    pub fn is_positive(&self) -> bool {
+        self.value_ns_2 > 0.0
+    }
+}
+
+/// Formats as `Time2(1.000ns²)`. The number of decimals defaults to three and
+/// can be set with the precision, e.g. `{:.6?}`.
+impl std::fmt::Debug for Time2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        write!(f, "Time2({:.precision$}ns²)", self.value_ns_2)
+    }
 }
 
 impl std::ops::Neg for Time2 {
@@ -319,4 +640,528 @@ impl std::ops::Div<f64> for Time2 {
     fn div(self, rhs: f64) -> Self::Output {
         Self::Output { value_ns_2: self.value_ns_2 / rhs }
     }
-}
\ No newline at end of file
+}
+// =============================================================================
+
+impl From<Seconds> for Time {
+    fn from(Seconds(secs): Seconds) -> Self {
+        Time::secs(secs)
+    }
+}
+
+impl From<Time> for Seconds {
+    fn from(time: Time) -> Self {
+        Seconds(time.as_secs())
+    }
+}
+
+impl From<Nanos> for Time {
+    fn from(Nanos(nanos): Nanos) -> Self {
+        Time::nanos(nanos)
+    }
+}
+
+impl From<Time> for Nanos {
+    fn from(time: Time) -> Self {
+        Nanos(time.as_nanos())
+    }
+}
+
+// =============================================================================
+
+impl Interval {
+    pub fn new(start: Time, end: Time) -> Self {
+        Self { start, end }
+    }
+
+    /// End - Start, or zero if the interval is empty
+    pub fn length(&self) -> Time {
+        Time::max(self.end - self.start, Time::zero())
+    }
+
+    pub fn contains(&self, time: Time) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+// =============================================================================
+
+impl TimeSpan {
+    pub fn zero() -> Self {
+        Self(Time::zero())
+    }
+
+    pub fn as_time(&self) -> Time {
+        self.0
+    }
+}
+
+impl TryFrom<Time> for TimeSpan {
+    type Error = crate::error::RTError;
+
+    fn try_from(time: Time) -> Result<Self, Self::Error> {
+        if time.value_ns >= 0.0 {
+            Ok(Self(time))
+        } else {
+            Err(crate::error::RTError::NegativeTime)
+        }
+    }
+}
+
+impl From<TimeSpan> for Time {
+    fn from(span: TimeSpan) -> Self {
+        span.0
+    }
+}
+
+impl std::ops::Add for TimeSpan {
+    type Output = TimeSpan;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+/// Signed difference between the two spans.
+impl std::ops::Sub for TimeSpan {
+    type Output = Time;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0 - rhs.0
+    }
+}
+
+impl std::ops::Mul<f64> for TimeSpan {
+    type Output = Time;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        self.0 * rhs
+    }
+}
+
+// =============================================================================
+
+/// ISO 8601 duration representation of `Time`, e.g. `PT0.005S`.
+///
+/// To be used through the serde `with` attribute, as in `#[serde(with =
+/// "eva_rt_common::time::iso8601")]`. Durations are serialized in seconds,
+/// while the `PnDTnHnMnS` subset of the format is accepted when deserializing.
+/// Years, months and weeks are rejected, as their length is not fixed. Negative
+/// durations are prefixed with a minus sign.
+pub mod iso8601 {
+    use super::Time;
+
+    pub fn serialize<S>(time: &Time, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let sign = if time.value_ns < 0.0 { "-" } else { "" };
+        serializer.serialize_str(&format!("{sign}PT{}S", time.as_secs().abs()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Time, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let duration = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+        parse(&duration)
+            .ok_or_else(|| serde::de::Error::custom(format!("Invalid ISO 8601 duration: {duration}")))
+    }
+
+    fn parse(duration: &str) -> Option<Time> {
+        let (sign, duration) = match duration.strip_prefix('-') {
+            Some(duration) => (-1.0, duration),
+            None => (1.0, duration),
+        };
+
+        let duration = duration.strip_prefix('P')?;
+        let (date, time) = match duration.split_once('T') {
+            Some((_, "")) => return None,
+            Some((date, time)) => (date, time),
+            None => (duration, ""),
+        };
+
+        let mut secs = 0.0;
+        let mut components = 0;
+        for (mut part, designators) in [
+            (date, &[('D', 86_400.0)][..]),
+            (time, &[('H', 3_600.0), ('M', 60.0), ('S', 1.0)][..]),
+        ] {
+            for &(designator, scale) in designators {
+                if let Some((value, rest)) = part.split_once(designator) {
+                    secs += value.parse::<f64>().ok()? * scale;
+                    components += 1;
+                    part = rest;
+                }
+            }
+
+            if !part.is_empty() {
+                return None;
+            }
+        }
+
+        if components == 0 {
+            return None;
+        }
+
+        Some(Time::secs(sign * secs))
+    }
+}
+
+// =============================================================================
+
+/// Structured representation of `Time`, e.g. `{ value = 5.0, unit = "ms" }`,
+/// for configuration formats which prefer objects over strings.
+///
+/// To be used through the serde `with` attribute, as in `#[serde(with =
+/// "eva_rt_common::time::as_struct")]`. Times are serialized in the largest of
+/// `s`, `ms`, `us` and `ns` in which their magnitude is at least one, or in
+/// nanoseconds if there is none.
+pub mod as_struct {
+    use super::{Time, TimeUnit};
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct TimeStruct {
+        value: f64,
+        unit: TimeUnit,
+    }
+
+    pub fn serialize<S>(time: &Time, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let unit = [TimeUnit::S, TimeUnit::Ms, TimeUnit::Us]
+            .into_iter()
+            .find(|&unit| time.as_unit(unit).abs() >= 1.0)
+            .unwrap_or(TimeUnit::Ns);
+
+        serde::Serialize::serialize(&TimeStruct { value: time.as_unit(unit), unit }, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Time, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let TimeStruct { value, unit } = serde::Deserialize::deserialize(deserializer)?;
+
+        Ok(Time::of(value, unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squared_equals_product() {
+        for value in [0.0, 1.5, -3.0, 1e6] {
+            let time = Time::nanos(value);
+            assert_eq!(time.squared().value(), (time * time).value());
+        }
+
+        assert_eq!(Time::micros(2.0).squared().value(), 4e6);
+    }
+
+    #[test]
+    fn powf_of_nanoseconds() {
+        assert_eq!(Time::nanos(9.0).powf(0.5), 3.0);
+        assert_eq!(Time::nanos(2.0).powf(3.0), 8.0);
+        assert_eq!(Time::micros(1.0).powf(1.0), 1000.0);
+    }
+
+    #[test]
+    fn try_as_integer_nanos_guards() {
+        assert_eq!(Time::nanos(5.7).try_as_u64_nanos(), Some(5));
+        assert_eq!(Time::nanos(0.0).try_as_u64_nanos(), Some(0));
+        assert_eq!(Time::nanos(-0.5).try_as_u64_nanos(), None);
+        assert_eq!(Time::nanos(f64::NAN).try_as_u64_nanos(), None);
+        assert_eq!(Time::nanos(f64::INFINITY).try_as_u64_nanos(), None);
+        assert_eq!(Time::nanos(1e20).try_as_u64_nanos(), None);
+        assert_eq!(Time::nanos(u64::MAX as f64).try_as_u64_nanos(), None);
+
+        assert_eq!(Time::nanos(-5.5).try_as_i64_nanos(), Some(-6));
+        assert_eq!(Time::nanos(5.5).try_as_i64_nanos(), Some(5));
+        assert_eq!(Time::nanos(f64::NAN).try_as_i64_nanos(), None);
+        assert_eq!(Time::nanos(f64::NEG_INFINITY).try_as_i64_nanos(), None);
+        assert_eq!(Time::nanos(1e19).try_as_i64_nanos(), None);
+        assert_eq!(Time::nanos(-1e19).try_as_i64_nanos(), None);
+    }
+
+    #[test]
+    fn format_fixed_is_aligned_in_the_given_unit() {
+        assert_eq!(Time::millis(1.5).format_fixed(12, TimeUnit::Us), "  1500.000us");
+        assert_eq!(Time::millis(1.5).format_fixed(12, TimeUnit::Ms), "     1.500ms");
+        assert_eq!(Time::nanos(42.0).format_fixed(12, TimeUnit::S), "      0.000s");
+        assert_eq!(Time::secs(2.0).format_fixed(0, TimeUnit::Ns), "2000000000.000ns");
+
+        let column: Vec<_> = [Time::nanos(5.0), Time::micros(3.25), Time::millis(7.0)]
+            .iter()
+            .map(|time| time.format_fixed(16, TimeUnit::Us))
+            .collect();
+        assert!(column.iter().all(|cell| cell.len() == 16));
+        assert!(column.iter().all(|cell| cell.ends_with("us")));
+    }
+
+    #[test]
+    fn format_fixed_prints_non_finite_without_unit() {
+        assert_eq!(Time::nanos(f64::NAN).format_fixed(6, TimeUnit::Ms), "   NaN");
+        assert_eq!(Time::nanos(f64::INFINITY).format_fixed(6, TimeUnit::Ms), "   inf");
+        assert_eq!(Time::nanos(f64::NEG_INFINITY).format_fixed(6, TimeUnit::Ms), "  -inf");
+    }
+
+    #[test]
+    fn cycles_round_trip_at_one_gigahertz() {
+        const HZ: f64 = 1e9;
+
+        assert_eq!(Time::from_cycles(1, HZ), Time::nanos(1.0));
+        assert_eq!(Time::from_cycles(2_500_000, HZ), Time::millis(2.5));
+        assert_eq!(Time::millis(2.5).to_cycles(HZ), 2_500_000.0);
+
+        for cycles in [0, 1, 999, 123_456_789, 1 << 40] {
+            let round_trip = Time::from_cycles(cycles, HZ).to_cycles(HZ);
+            assert!((round_trip - cycles as f64).abs() <= cycles as f64 * 1e-12);
+        }
+
+        assert_eq!(Time::from_cycles(3, 3e9), Time::nanos(1.0));
+    }
+
+    #[test]
+    fn debug_shows_the_scaled_value() {
+        assert_eq!(format!("{:?}", Time::millis(1.0)), "Time(1.000ms)");
+        assert_eq!(format!("{:?}", Time::nanos(12.0)), "Time(12.000ns)");
+        assert_eq!(format!("{:.1?}", Time::micros(2.25)), "Time(2.2us)");
+        assert_eq!(format!("{:?}", Some(Time::secs(3.0))), "Some(Time(3000.000ms))");
+
+        assert_eq!(format!("{:?}", Time2::new(4.0)), "Time2(4.000ns²)");
+        assert_eq!(format!("{:.0?}", Time2::new(1e6)), "Time2(1000000ns²)");
+    }
+
+    #[test]
+    fn iso8601_round_trip() {
+        use crate::test_utils::{Value, ValueSerializer};
+
+        let to_iso = |time: Time| match iso8601::serialize(&time, ValueSerializer).unwrap() {
+            Value::Str(duration) => duration,
+            value => panic!("{value:?} is not a string"),
+        };
+        let from_iso = |duration: &str| iso8601::deserialize(Value::Str(duration.to_string()));
+
+        assert_eq!(to_iso(Time::millis(5.0)), "PT0.005S");
+        assert_eq!(to_iso(Time::secs(2.0)), "PT2S");
+        assert_eq!(to_iso(Time::millis(-250.0)), "-PT0.25S");
+
+        for time in [Time::millis(5.0), Time::millis(1500.0), Time::secs(2.0), Time::secs(-3.5)] {
+            assert_eq!(from_iso(&to_iso(time)).unwrap(), time);
+        }
+
+        assert_eq!(from_iso("PT1M30S").unwrap(), Time::secs(90.0));
+        assert_eq!(from_iso("P1DT1H").unwrap(), Time::secs(90_000.0));
+        for invalid in ["", "PT", "P", "T5S", "PT5", "PT5X", "5S"] {
+            assert!(from_iso(invalid).is_err(), "{invalid}");
+        }
+
+        // The default representation is unchanged.
+        let default = Value::from(&Time::millis(5.0));
+        assert_eq!(default, Value::Str("5000000 ns".to_string()));
+        assert_eq!(default.deserialize::<Time>().unwrap(), Time::millis(5.0));
+    }
+
+    #[test]
+    fn typed_float_conversions() {
+        assert_eq!(Time::from(Seconds(0.5)), Time::millis(500.0));
+        assert_eq!(Time::from(Nanos(42.0)), Time::nanos(42.0));
+        assert_eq!(Seconds::from(Time::millis(250.0)), Seconds(0.25));
+        assert_eq!(Nanos::from(Time::micros(3.0)), Nanos(3000.0));
+
+        let time = Time::micros(1.5);
+        assert_eq!(Time::from(Seconds::from(time)), time);
+        assert_eq!(Time::from(Nanos::from(time)), time);
+    }
+
+    #[test]
+    fn display_of_non_finite_times() {
+        assert_eq!(Time::nanos(f64::NAN).to_string(), "NaN");
+        assert_eq!(Time::nanos(f64::INFINITY).to_string(), "inf");
+        assert_eq!(Time::nanos(f64::NEG_INFINITY).to_string(), "-inf");
+        assert_eq!(format!("{:?}", Time::nanos(f64::NAN)), "Time(NaN)");
+        assert_eq!((Time::zero() / 0.0).to_string(), "NaN");
+    }
+
+    #[test]
+    fn time2_sign_predicates() {
+        let negative = Time2::new(-4.0);
+        let positive = Time2::from_product(Time::nanos(2.0), Time::nanos(3.0));
+        let zero = Time2::new(0.0);
+
+        assert_eq!(negative.abs().value(), 4.0);
+        assert_eq!(positive.abs().value(), 6.0);
+        assert_eq!((-positive).abs().value(), 6.0);
+
+        assert!(negative.is_negative() && !negative.is_positive());
+        assert!(positive.is_positive() && !positive.is_negative());
+        assert!(!zero.is_negative() && !zero.is_positive());
+        assert_eq!(negative.abs().sqrt(), Time::nanos(2.0));
+    }
+
+    #[test]
+    fn time_span_conversion_guards() {
+        use crate::error::RTError;
+        use crate::test_utils::Value;
+
+        let span = TimeSpan::try_from(Time::millis(2.0)).unwrap();
+        assert_eq!(Time::from(span), Time::millis(2.0));
+        assert_eq!(span.as_time(), Time::millis(2.0));
+        assert_eq!(TimeSpan::try_from(Time::zero()), Ok(TimeSpan::zero()));
+
+        assert_eq!(TimeSpan::try_from(Time::nanos(-1.0)), Err(RTError::NegativeTime));
+        assert_eq!(TimeSpan::try_from(Time::nanos(f64::NAN)), Err(RTError::NegativeTime));
+
+        // Differences of spans are signed.
+        let longer = TimeSpan::try_from(Time::millis(3.0)).unwrap();
+        assert_eq!(span - longer, Time::millis(-1.0));
+        assert_eq!(Time::from(span + longer), Time::millis(5.0));
+
+        let deserialize = |time: &str| Value::Str(time.to_string()).deserialize::<TimeSpan>();
+        assert_eq!(deserialize("5 ms").unwrap(), TimeSpan::try_from(Time::millis(5.0)).unwrap());
+        assert!(deserialize("-5 ms").is_err());
+    }
+
+    #[test]
+    fn integer_nanoseconds_detection() {
+        let tol = Time::nanos(1e-6);
+
+        assert!(Time::nanos(5.0).is_integer_ns(tol));
+        assert!(Time::nanos(5.0 + 1e-7).is_integer_ns(tol));
+        assert!(Time::nanos(5.0 - 1e-7).is_integer_ns(tol));
+        assert!(!Time::nanos(5.0 + 1e-3).is_integer_ns(tol));
+        assert!(!Time::nanos(4.5).is_integer_ns(tol));
+        assert!(Time::nanos(4.5).is_integer_ns(Time::nanos(0.5)));
+        assert!(!Time::nanos(f64::NAN).is_integer_ns(tol));
+
+        assert_eq!(Time::nanos(5.0 - 1e-7).nearest_integer_ns(), 5);
+        assert_eq!(Time::nanos(5.4).nearest_integer_ns(), 5);
+        assert_eq!(Time::nanos(5.6).nearest_integer_ns(), 6);
+        assert_eq!(Time::nanos(-2.6).nearest_integer_ns(), -3);
+        assert_eq!(Time::nanos(1e30).nearest_integer_ns(), i64::MAX);
+        assert_eq!(Time::nanos(f64::NAN).nearest_integer_ns(), 0);
+    }
+
+    #[test]
+    fn assert_time_eq_passes_within_tolerance() {
+        crate::assert_time_eq!(Time::millis(1.0), Time::millis(1.0) + Time::nanos(0.9), Time::nanos(1.0));
+        crate::assert_time_eq!(Time::secs(1.0), Time::millis(1000.0), Time::zero());
+        crate::assert_time_eq!(Time::micros(5.0), Time::micros(5.1), Time::micros(0.1), "step {}", 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "(tolerance Time(1.000ns))\n  left: Time(1.000ms)\n right: Time(1.002ms)")]
+    fn assert_time_eq_fails_beyond_tolerance() {
+        crate::assert_time_eq!(Time::millis(1.0), Time::millis(1.002), Time::nanos(1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "(tolerance Time(0.000ns)): step 3\n")]
+    fn assert_time_eq_fails_with_message() {
+        crate::assert_time_eq!(Time::nanos(1.0), Time::nanos(2.0), Time::zero(), "step {}", 3);
+    }
+
+    #[test]
+    fn time2_constructors_match_the_operators() {
+        let values = [0.0, 1.5, -2.0, 1e9, 3e-3];
+        for a in values {
+            for b in values {
+                let (a, b) = (Time::nanos(a), Time::nanos(b));
+                assert_eq!(Time2::from_product(a, b).value(), (a * b).value());
+                assert_eq!(Time2::from_product(a, b).value(), Time2::from_product(b, a).value());
+            }
+
+            let t = Time::nanos(a);
+            assert_eq!(Time2::from_time(t).value(), (t * t).value());
+            assert_eq!(Time2::from_time(t).sqrt(), Time::nanos(a.abs()));
+            assert_eq!(Time2::from_product(Time::millis(2.0), t) / Time::millis(2.0), t);
+        }
+    }
+
+    #[test]
+    fn time_in_each_unit() {
+        let time = Time::millis(1.5);
+        let units = [
+            (TimeUnit::Ns, Time::nanos(1.0), time.as_nanos()),
+            (TimeUnit::Us, Time::micros(1.0), time.as_micros()),
+            (TimeUnit::Ms, Time::millis(1.0), time.as_millis()),
+            (TimeUnit::S, Time::secs(1.0), time.as_secs()),
+        ];
+
+        for (unit, one, named) in units {
+            assert_eq!(Time::of(1.0, unit), one);
+            assert_eq!(Time::of(2.5, unit), one * 2.5);
+            assert_eq!(time.as_unit(unit), named);
+            assert_eq!(Time::of(time.as_unit(unit), unit), time);
+            assert_eq!(format!("1{}", unit.suffix()).parse::<Time>().unwrap(), one);
+        }
+
+        // Units accepted by the parser only
+        assert_eq!("2d".parse::<Time>().unwrap(), Time::secs(2.0 * 86400.0));
+        assert_eq!("1.5 h".parse::<Time>().unwrap(), Time::secs(5400.0));
+        assert_eq!("3min".parse::<Time>().unwrap(), Time::secs(180.0));
+    }
+
+    #[test]
+    fn display_of_day_scale_and_larger_times() {
+        const DAY: f64 = 86400.0;
+
+        assert_eq!(Time::secs(2.5 * DAY).to_string(), "2.500d");
+        assert_eq!(Time::secs(DAY - 3600.0).to_string(), "23.000h");
+        assert_eq!(Time::secs(1000.0).to_string(), "16.667min");
+        assert_eq!(Time::secs(365.0 * DAY).to_string(), "365.000d");
+        assert_eq!(Time::secs(1e6 * DAY).to_string(), "1.000e6d");
+        assert_eq!(Time::secs(2.5e12 * DAY).to_string(), "2.500e12d");
+
+        // Negative times are promoted by magnitude
+        assert_eq!(Time::secs(-DAY).to_string(), "-1.000d");
+        assert_eq!(Time::secs(-3.0 * 3600.0).to_string(), "-3.000h");
+        assert_eq!(Time::secs(-4e7 * DAY).to_string(), "-4.000e7d");
+
+        // Below a million milliseconds, the unit is not promoted
+        assert_eq!(Time::secs(1.5).to_string(), "1500.000ms");
+        assert_eq!(Time::secs(999.0).to_string(), "999000.000ms");
+        assert_eq!(Time::secs(-90.0).to_string(), "-90000.000ms");
+
+        // The printed value parses back
+        for time in [Time::secs(2.5 * DAY), Time::secs(-4e7 * DAY), Time::secs(1000.5)] {
+            assert_eq!(time.to_string().parse::<Time>().unwrap(), time);
+        }
+    }
+
+    #[test]
+    fn as_struct_round_trip() {
+        use crate::test_utils::{Value, ValueSerializer};
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(with = "as_struct")]
+            period: Time,
+        }
+
+        let structured = |value: f64, unit: &str| Value::Map(vec![
+            ("value".to_string(), Value::F64(value)),
+            ("unit".to_string(), Value::Str(unit.to_string())),
+        ]);
+
+        // The largest unit with magnitude at least one
+        assert_eq!(as_struct::serialize(&Time::millis(5.0), ValueSerializer).unwrap(), structured(5.0, "ms"));
+        assert_eq!(as_struct::serialize(&Time::secs(-2.0), ValueSerializer).unwrap(), structured(-2.0, "s"));
+        assert_eq!(as_struct::serialize(&Time::micros(1.5), ValueSerializer).unwrap(), structured(1.5, "us"));
+        assert_eq!(as_struct::serialize(&Time::nanos(0.5), ValueSerializer).unwrap(), structured(0.5, "ns"));
+        assert_eq!(as_struct::serialize(&Time::zero(), ValueSerializer).unwrap(), structured(0.0, "ns"));
+
+        for time in [Time::millis(5.0), Time::secs(-2.0), Time::micros(1.5), Time::nanos(0.5), Time::secs(3600.0)] {
+            let config: Config = Value::from(&Config { period: time }).deserialize().unwrap();
+            assert_eq!(config.period, time);
+        }
+
+        assert_eq!(as_struct::deserialize(structured(2.5, "us")).unwrap(), Time::nanos(2500.0));
+        assert!(as_struct::deserialize(structured(2.5, "h")).is_err());
+    }
+}
@@ -4,17 +4,70 @@
 //! provides utility functions to compute properties of the given tasksets.
 
 use crate::prelude::*;
+use crate::simulation::{EdfScheduler, Timeline};
 
 pub mod prelude {
     pub use super::{
         RTUtils,
+        SchedulabilityTest,
+        SchedulingPolicy,
+        TimeQueue,
+        IncrementalAnalyzer,
     };
 }
 
 /// Utility functions on tasksets.
 pub struct RTUtils;
 
+/// Boxed schedulability test, as returned by `RTUtils::test_by_name`.
+pub type SchedulabilityTest = Box<dyn Fn(&[RTTask]) -> bool>;
+
+type TestFn = fn(&[RTTask]) -> bool;
+
+/// Scheduling policy, as analysed by `RTUtils::is_schedulable`.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub enum SchedulingPolicy {
+    /// Fixed priorities by increasing period
+    RateMonotonic,
+    /// Fixed priorities by increasing deadline
+    DeadlineMonotonic,
+    /// Earliest Deadline First
+    Edf,
+    /// Fixed priorities assigned by Audsley's algorithm
+    FixedPriorityOpa,
+}
+
+/// Schedulability tests selectable by name, see `RTUtils::test_by_name`.
+const SCHEDULABILITY_TESTS: &[(&str, TestFn)] = &[
+    ("edf", RTUtils::edf_schedulable),
+    ("edf-qpa", RTUtils::edf_qpa),
+    ("rm-ll", RTUtils::rm_liu_layland),
+    ("fp-feasible", RTUtils::is_feasible_fp),
+    ("rm-sr", RTUtils::sr_bound_test),
+    ("edf-density", RTUtils::edf_density_test),
+];
+
+/// Maximum number of iterations of the response-time recurrences, after which
+/// the analysed task is deemed unschedulable.
+pub(crate) const MAX_RESPONSE_TIME_ITERATIONS: u64 = 1_000_000;
+
 impl RTUtils {
+    /// Check that every task has finite parameters, a non-negative WCET and
+    /// a positive deadline and period.
+    pub fn validate(taskset: &[RTTask]) -> Result<(), RTError> {
+        let positive = |time: Time| time.value_ns.is_finite() && time.value_ns > 0.0;
+
+        match taskset.iter().position(|task| {
+            !task.wcet.value_ns.is_finite() || task.wcet.value_ns < 0.0 ||
+            !positive(task.deadline) || !positive(task.period)
+        }) {
+            Some(index) => Err(RTError::InvalidTask(index)),
+            None => Ok(()),
+        }
+    }
+
     pub fn is_taskset_sorted_by_period(taskset: &[RTTask]) -> bool {
         taskset.windows(2).all(|w| w[0].period <= w[1].period)
     }
@@ -23,6 +76,22 @@ impl RTUtils {
         taskset.windows(2).all(|w| w[0].deadline <= w[1].deadline)
     }
 
+    /// Sorted copy of the taskset, following the canonical `RTTask` ordering.
+    pub fn canonicalize(taskset: &[RTTask]) -> Vec<RTTask> {
+        let mut taskset = taskset.to_vec();
+        taskset.sort();
+        taskset
+    }
+
+    /// Sort the taskset in canonical order and remove duplicate tasks, as
+    /// defined by `RTTask: Eq`, i.e. tasks whose parameters round to the same
+    /// integer nanoseconds. The result thus agrees with collecting the tasks
+    /// in a `HashSet` or `BTreeSet`.
+    pub fn dedup(taskset: &mut Vec<RTTask>) {
+        taskset.sort();
+        taskset.dedup();
+    }
+
     pub fn implicit_deadlines(taskset: &[RTTask]) -> bool {
         taskset.iter().all(RTTask::has_implicit_deadline)
     }
@@ -31,9 +100,122 @@ impl RTUtils {
         taskset.iter().all(RTTask::has_constrained_deadline)
     }
 
+    /// Copy of the taskset with every deadline clamped to `min(D, T)`, so that
+    /// it satisfies `constrained_deadlines`. Note that this changes the
+    /// taskset: jobs of the tasks with arbitrary deadlines are required to
+    /// complete earlier, so the result is harder to schedule than the
+    /// original.
+    pub fn enforce_constrained(taskset: &[RTTask]) -> Vec<RTTask> {
+        taskset.iter()
+            .map(|task| RTTask {
+                deadline: Time::min(task.deadline, task.period),
+                ..task.clone()
+            })
+            .collect()
+    }
+
+    /// Copy of the taskset with every period multiplied by `factor`, e.g. to
+    /// model frequency scaling. If `keep_implicit`, deadlines are set to the
+    /// new periods; otherwise they are left unchanged.
+    pub fn scale_periods(taskset: &[RTTask], factor: f64, keep_implicit: bool) -> Vec<RTTask> {
+        taskset.iter()
+            .map(|task| {
+                let period = task.period * factor;
+                RTTask {
+                    deadline: if keep_implicit { period } else { task.deadline },
+                    period,
+                    ..task.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Copy of the taskset, in the same order, without the tasks of lowest
+    /// value, which are greedily removed until the total utilization is at
+    /// most `target_u`. Among tasks of equal value, the later, i.e. lower
+    /// priority, ones are removed first.
+    pub fn shed_to_utilization(
+        taskset: &[RTTask],
+        target_u: f64,
+        value: impl Fn(&RTTask) -> f64,
+    ) -> Vec<RTTask> {
+        let mut order: Vec<usize> = (0..taskset.len()).collect();
+        order.sort_by(|&a, &b| {
+            f64::total_cmp(&value(&taskset[a]), &value(&taskset[b]))
+                .then(b.cmp(&a))
+        });
+
+        let mut removed = vec![false; taskset.len()];
+        let mut order = order.into_iter();
+        loop {
+            let kept: Vec<RTTask> = taskset.iter()
+                .zip(&removed)
+                .filter(|&(_, &removed)| !removed)
+                .map(|(task, _)| task.clone())
+                .collect();
+
+            match order.next() {
+                Some(i) if Self::total_utilization(&kept) > target_u => removed[i] = true,
+                _ => return kept,
+            }
+        }
+    }
+
     pub fn total_utilization(taskset: &[RTTask]) -> f64 {
+        compensated_sum(taskset.iter()
+            .map(RTTask::utilization))
+    }
+
+    /// Weighted total utilization, `sum(w_i * U_i)`, e.g. by criticality or
+    /// priority. With unit weights, it is the same as `total_utilization`.
+    ///
+    /// Panics if there is not exactly one weight per task.
+    pub fn weighted_utilization(taskset: &[RTTask], weights: &[f64]) -> f64 {
+        assert_eq!(taskset.len(), weights.len(), "There must be one weight per task");
+
+        compensated_sum(taskset.iter()
+            .zip(weights)
+            .map(|(task, weight)| weight * task.utilization()))
+    }
+
+    /// `prod(U_i + 1)`, as used by the hyperbolic bound for Rate Monotonic
+    /// (Bini et al., 2003), which accepts when it is at most two. It is one
+    /// for an empty taskset.
+    pub fn utilization_product_plus_one(taskset: &[RTTask]) -> f64 {
+        taskset.iter()
+            .map(|task| task.utilization() + 1.0)
+            .product()
+    }
+
+    /// Total utilization of each core of a partitioned taskset, where
+    /// `partition[core]` holds the tasks assigned to that core.
+    pub fn core_utilizations(partition: &[Vec<RTTask>]) -> Vec<f64> {
+        partition.iter()
+            .map(|taskset| Self::total_utilization(taskset))
+            .collect()
+    }
+
+    pub fn try_total_utilization(taskset: &[RTTask]) -> Result<f64, RTError> {
+        Self::validate(taskset)?;
+
+        Ok(Self::total_utilization(taskset))
+    }
+
+    /// Total utilization as an exact rational number, computed from the WCETs
+    /// and periods rounded to integer nanoseconds. Unlike `total_utilization`,
+    /// comparisons against one are not affected by floating point rounding.
+    ///
+    /// Panics if any WCET or period is not representable as an `u64` of
+    /// nanoseconds, or if a period rounds to zero.
+    pub fn total_utilization_exact(taskset: &[RTTask]) -> num::BigRational {
+        let integer_nanos = |time: Time| -> num::BigInt {
+            time.round().try_as_u64_nanos()
+                .expect("Time is not representable in integer nanoseconds")
+                .into()
+        };
+
         taskset.iter()
-            .map(RTTask::utilization)
+            .map(|task| num::BigRational::new(integer_nanos(task.wcet), integer_nanos(task.period)))
             .sum()
     }
 
@@ -48,10 +230,23 @@ impl RTUtils {
         }
     }
 
+    /// Total utilization of the tasks in each period band. The `bands` are
+    /// the sorted boundaries between consecutive bands, so `n` boundaries
+    /// define `n + 1` bands: periods shorter than the first boundary, periods
+    /// in [b0, b1), ..., periods not shorter than the last boundary.
+    pub fn utilization_by_band(taskset: &[RTTask], bands: &[Time]) -> Vec<f64> {
+        let mut utilizations = vec![0.0; bands.len() + 1];
+        for task in taskset {
+            let band = bands.partition_point(|&bound| bound <= task.period);
+            utilizations[band] += task.utilization();
+        }
+
+        utilizations
+    }
+
     pub fn total_density(taskset: &[RTTask]) -> f64 {
-        taskset.iter()
-            .map(RTTask::density)
-            .sum()
+        compensated_sum(taskset.iter()
+            .map(RTTask::density))
     }
 
     pub fn largest_density(taskset: &[RTTask]) -> f64 {
@@ -65,12 +260,2521 @@ impl RTUtils {
         }
     }
 
+    /// Largest period / Smallest period, or one for an empty taskset.
+    pub fn period_ratio(taskset: &[RTTask]) -> f64 {
+        let min = taskset.iter().map(|task| task.period).min();
+        let max = taskset.iter().map(|task| task.period).max();
+
+        match (min, max) {
+            (Some(min), Some(max)) => max / min,
+            _ => 1.0,
+        }
+    }
+
+    /// Largest utilization - Smallest utilization, or zero for an empty
+    /// taskset.
+    pub fn utilization_spread(taskset: &[RTTask]) -> f64 {
+        let utilizations = || taskset.iter()
+            .map(|task| ordered_float::OrderedFloat(task.utilization()));
+
+        match (utilizations().min(), utilizations().max()) {
+            (Some(min), Some(max)) => *max - *min,
+            _ => 0f64,
+        }
+    }
+
+    pub fn laxity_vector(taskset: &[RTTask]) -> Vec<Time> {
+        taskset.iter()
+            .map(RTTask::laxity)
+            .collect()
+    }
+
+    pub fn min_laxity(taskset: &[RTTask]) -> Option<Time> {
+        taskset.iter()
+            .map(RTTask::laxity)
+            .min()
+    }
+
+    /// Sum of times with compensated summation, which does not accumulate
+    /// rounding errors on long sequences of values. `Sum` for `Time` uses the
+    /// same algorithm.
+    pub fn kahan_sum(times: &[Time]) -> Time {
+        times.iter().copied().sum()
+    }
+
+    /// Least common multiple of the periods, rounded down to integer
+    /// nanoseconds.
+    ///
+    /// Panics if any period is not representable as an `u64` of nanoseconds,
+    /// or if the result overflows. See `try_hyperperiod` for a non-panicking
+    /// version.
     pub fn hyperperiod(taskset: &[RTTask]) -> Time {
-        let hyperperiod =
+        taskset.iter()
+            .fold(Time::one(), |hyperperiod, task| time_lcm(hyperperiod, task.period)
+                .expect("Hyperperiod is not representable in integer nanoseconds"))
+    }
+
+    pub fn try_hyperperiod(taskset: &[RTTask]) -> Result<Time, RTError> {
+        if taskset.is_empty() {
+            return Err(RTError::EmptyTaskset);
+        }
+
+        Self::validate(taskset)?;
+
+        let mut hyperperiod: u64 = 1;
+        for (index, task) in taskset.iter().enumerate() {
+            let period = match task.period.try_as_u64_nanos() {
+                Some(0) | None => return Err(RTError::InvalidTask(index)),
+                Some(period) => period,
+            };
+
+            hyperperiod = checked_lcm(hyperperiod, period)
+                .ok_or(RTError::Overflow)?;
+        }
+
+        Ok(Time::nanos(hyperperiod as f64))
+    }
+
+    /// Greatest common divisor of the periods, in integer nanoseconds: the
+    /// longest tick of which every period is a multiple. Zero for an empty
+    /// taskset.
+    ///
+    /// Panics if any period is not representable as an `u64` of nanoseconds.
+    pub fn base_tick(taskset: &[RTTask]) -> Time {
+        taskset.iter()
+            .fold(Time::zero(), |tick, task| time_gcd(tick, task.period)
+                .expect("Period is not representable in integer nanoseconds"))
+    }
+
+    /// Length of each period in ticks of `base_tick`.
+    ///
+    /// Panics under the same conditions as `base_tick`, or if all the periods
+    /// of a non-empty taskset are zero.
+    pub fn ticks_per_period(taskset: &[RTTask]) -> Vec<u64> {
+        if taskset.is_empty() {
+            return Vec::new();
+        }
+
+        let tick = Self::base_tick(taskset).try_as_u64_nanos().unwrap();
+        assert!(tick > 0, "Base tick must be positive");
+
+        taskset.iter()
+            .map(|task| task.period.try_as_u64_nanos().unwrap() / tick)
+            .collect()
+    }
+
+    /// Merge harmonically related tasks into a smaller, equivalent taskset.
+    ///
+    /// Every implicit-deadline task whose period is a multiple of the period
+    /// of an already merged task is folded into it, contributing its
+    /// utilization to the merged WCET. Tasks without implicit deadlines are
+    /// returned unchanged, after the merged ones.
+    ///
+    /// The total utilization is preserved, so the result is equivalent to the
+    /// original taskset for EDF with implicit deadlines. It is **not** sound
+    /// for fixed-priority analyses, as a merged task gets the priority of its
+    /// shortest period, unless all the merged tasks have the same period.
+    pub fn merge_harmonic(taskset: &[RTTask]) -> Vec<RTTask> {
+        let mut implicit: Vec<&RTTask> = taskset.iter()
+            .filter(|task| task.has_implicit_deadline())
+            .collect();
+        implicit.sort_by_key(|task| task.period);
+
+        let mut merged: Vec<RTTask> = Vec::new();
+        for task in implicit {
+            let base = merged.iter_mut()
+                .find(|base| task.period % base.period == Time::zero());
+
+            match base {
+                Some(base) => base.wcet = base.wcet + base.period * task.utilization(),
+                None => merged.push(task.clone()),
+            }
+        }
+
+        merged.extend(taskset.iter()
+            .filter(|task| !task.has_implicit_deadline())
+            .cloned());
+
+        merged
+    }
+
+    /// Partition of the task indices into harmonic chains, i.e. sequences in
+    /// which every period divides the following one. Each chain is sorted by
+    /// increasing period.
+    ///
+    /// Tasks are visited by increasing period and appended to the first chain
+    /// whose last period divides their own, or start a new chain otherwise.
+    pub fn harmonic_chains(taskset: &[RTTask]) -> Vec<Vec<usize>> {
+        let mut order: Vec<usize> = (0..taskset.len()).collect();
+        order.sort_by_key(|&i| taskset[i].period);
+
+        let mut chains: Vec<Vec<usize>> = Vec::new();
+        for i in order {
+            let chain = chains.iter_mut()
+                .find(|chain| {
+                    let last = &taskset[*chain.last().unwrap()];
+                    taskset[i].period % last.period == Time::zero()
+                });
+
+            match chain {
+                Some(chain) => chain.push(i),
+                None => chains.push(vec![i]),
+            }
+        }
+
+        chains
+    }
+
+    /// Demand Bound Function: total execution demand of the jobs with both
+    /// release time and absolute deadline in [0, t], under synchronous release.
+    pub fn dbf(taskset: &[RTTask], t: Time) -> Time {
+        taskset.iter()
+            .filter(|task| t >= task.deadline)
+            .map(|task| task.wcet * (f64::floor((t - task.deadline) / task.period) + 1.0))
+            .sum()
+    }
+
+    /// Absolute deadlines `k * T + D` up to the bound, included, sorted and
+    /// without duplicates: the only points at which the dbf can increase, and
+    /// thus the only ones an exact EDF test needs to check.
+    pub fn testing_points(taskset: &[RTTask], bound: Time) -> Vec<Time> {
+        let mut points: Vec<Time> = taskset.iter()
+            .filter(|task| task.deadline <= bound)
+            .flat_map(|task| (0..=f64::floor((bound - task.deadline) / task.period) as u64)
+                .map(|job| task.deadline + task.period * job as f64))
+            .collect();
+
+        points.sort();
+        points.dedup();
+        points
+    }
+
+    /// Length of the synchronous busy period. Returns `None` if the total
+    /// utilization is greater than one, as the busy period is unbounded, or if
+    /// it is not found within the iteration limit of `rbf_fixpoint`.
+    pub fn busy_period(taskset: &[RTTask]) -> Option<Time> {
+        match taskset.len() {
+            0 => Some(Time::zero()),
+            n => Self::rbf_fixpoint(taskset, n - 1),
+        }
+    }
+
+    /// Request Bound Function of the first `i + 1` tasks, i.e. the total
+    /// execution time requested in a window of length `t` by task `i` and the
+    /// tasks of higher priority, with tasks sorted by decreasing priority.
+    pub fn rbf(taskset: &[RTTask], i: usize, t: Time) -> Time {
+        taskset[..=i].iter()
+            .map(|task| task.interference(t))
+            .sum()
+    }
+
+    /// Smallest positive solution of `t = rbf(taskset, i, t)`, i.e. the length
+    /// of the level-i busy period, found by fixed-point iteration. Returns
+    /// `None` if the utilization of the first `i + 1` tasks is greater than
+    /// one, as the iteration would not converge, or if it does not converge
+    /// within `MAX_RESPONSE_TIME_ITERATIONS` iterations, as may happen when the
+    /// utilization is one and the busy period spans a huge hyperperiod.
+    pub fn rbf_fixpoint(taskset: &[RTTask], i: usize) -> Option<Time> {
+        if Self::total_utilization(&taskset[..=i]) > 1.0 {
+            return None;
+        }
+
+        let mut t: Time = taskset[..=i].iter().map(|task| task.wcet).sum();
+        for _ in 0..MAX_RESPONSE_TIME_ITERATIONS {
+            let next = Self::rbf(taskset, i, t);
+            if next == t {
+                return Some(next);
+            }
+
+            t = next;
+        }
+
+        None
+    }
+
+    /// Copy of the taskset sorted by Deadline Monotonic priority, i.e. by
+    /// increasing deadline. Tasks with equal deadlines keep their order.
+    pub fn deadline_monotonic(taskset: &[RTTask]) -> Vec<RTTask> {
+        let mut taskset = taskset.to_vec();
+        taskset.sort_by_key(|task| task.deadline);
+        taskset
+    }
+
+    /// Rate Monotonic priority of each task, where zero is the highest
+    /// priority and shorter periods get higher priorities. Tasks with equal
+    /// periods are prioritized by index, so all priorities are distinct.
+    pub fn rm_priorities(taskset: &[RTTask]) -> Vec<u32> {
+        let mut order: Vec<usize> = (0..taskset.len()).collect();
+        order.sort_by_key(|&i| taskset[i].period);
+
+        let mut priorities = vec![0; taskset.len()];
+        for (priority, i) in order.into_iter().enumerate() {
+            priorities[i] = priority as u32;
+        }
+
+        priorities
+    }
+
+    /// Worst-case response time of each task under fixed priorities, with
+    /// tasks sorted by decreasing priority. Returns `None` for the tasks whose
+    /// response time exceeds their deadline.
+    ///
+    /// Deadlines may be arbitrary: every job in the level-i busy period is
+    /// analysed, as in Lehoczky's analysis (1990). Tasks for which the higher
+    /// priority utilization is at least one, or the level-i utilization is
+    /// greater than one, are rejected without iterating, as their response
+    /// time is unbounded. The recurrence is otherwise iterated at most
+    /// `MAX_RESPONSE_TIME_ITERATIONS` times per task.
+    pub fn response_time_fp(taskset: &[RTTask]) -> Vec<Option<Time>> {
+        taskset.iter().enumerate()
+            .map(|(i, task)| Self::fixed_priority_response_time(task, &taskset[..i]))
+            .collect()
+    }
+
+    /// Response-time analysis under fixed priorities, as `response_time_fp`,
+    /// for a taskset in any order with explicit priorities, where lower values
+    /// are higher priorities as in `rm_priorities`. The results are keyed by
+    /// priority.
+    ///
+    /// Panics if there is not exactly one priority per task, or if any two
+    /// tasks have the same priority.
+    pub fn response_time_fp_by_priority(
+        taskset: &[RTTask],
+        priorities: &[u32],
+    ) -> std::collections::HashMap<u32, Option<Time>> {
+        assert_eq!(taskset.len(), priorities.len(), "There must be one priority per task");
+
+        let mut order: Vec<usize> = (0..taskset.len()).collect();
+        order.sort_by_key(|&i| priorities[i]);
+        assert!(order.windows(2).all(|pair| priorities[pair[0]] != priorities[pair[1]]),
+            "Priorities must be distinct");
+
+        let sorted: Vec<RTTask> = order.iter()
+            .map(|&i| taskset[i].clone())
+            .collect();
+
+        order.into_iter()
+            .map(|i| priorities[i])
+            .zip(Self::response_time_fp(&sorted))
+            .collect()
+    }
+
+    /// Response-time analysis under fixed priorities, as `response_time_fp`,
+    /// accounting for the given context-switch cost.
+    ///
+    /// Every job is charged two context switches, one when it preempts (or
+    /// starts after) a lower priority job and one when it completes, so each
+    /// WCET is inflated by twice the switch cost. The interference of higher
+    /// priority jobs and the execution of the analysed job are both affected.
+    pub fn response_time_fp_with_overhead(taskset: &[RTTask], switch_cost: Time) -> Vec<Option<Time>> {
+        let inflated: Vec<RTTask> = taskset.iter()
+            .map(|task| task.inflate_wcet(switch_cost * 2.0))
+            .collect();
+
+        Self::response_time_fp(&inflated)
+    }
+
+    /// Upper bound on the number of preemptions suffered by each task under
+    /// fixed priorities, with tasks sorted by decreasing priority: at most
+    /// one per higher priority job released during its response time, i.e.
+    /// `sum_{j < i} ceil(R_i / T_j)`. Returns `None` for the tasks whose
+    /// response time is not bounded, as in `response_time_fp`.
+    pub fn max_preemptions_bound(taskset: &[RTTask]) -> Vec<Option<u64>> {
+        Self::response_time_fp(taskset).into_iter()
+            .enumerate()
+            .map(|(i, response)| {
+                let response = response?;
+                Some(taskset[..i].iter()
+                    .map(|higher| f64::ceil(response / higher.period) as u64)
+                    .sum())
+            })
+            .collect()
+    }
+
+    /// Response-time analysis under fixed priorities, as `response_time_fp`,
+    /// accounting for cache-related preemption delays, where `crpd[j]` is the
+    /// delay caused on the preempted tasks by each job of task `j`.
+    ///
+    /// This is the simple model by Busquets-Mataix et al. (1996), in which
+    /// every higher priority job is charged the cost of one preemption:
+    /// `R_i = C_i + sum_{j < i} ceil(R_i / T_j) * (C_j + crpd_j)`. The cost of
+    /// the highest priority task is thus never used.
+    ///
+    /// Panics if there is not exactly one delay per task.
+    pub fn response_time_fp_crpd(taskset: &[RTTask], crpd: &[Time]) -> Vec<Option<Time>> {
+        assert_eq!(taskset.len(), crpd.len(), "There must be one preemption delay per task");
+
+        let inflated: Vec<RTTask> = taskset.iter()
+            .zip(crpd)
+            .map(|(task, &delay)| task.inflate_wcet(delay))
+            .collect();
+
+        // Each higher priority job interferes with its WCET and its delay
+        taskset.iter().enumerate()
+            .map(|(i, task)| Self::fixed_priority_response_time(task, &inflated[..i]))
+            .collect()
+    }
+
+    /// Where the response-time analysis of the i-th task fails, with tasks
+    /// sorted by decreasing priority: the absolute deadline of the first job
+    /// which misses it, and the higher priority tasks which release jobs
+    /// before that point. The demand of the level-i busy period, starting at
+    /// zero, exceeds the available time at that deadline.
+    ///
+    /// Returns `None` if the task meets its deadline. Panics if `i` is out of
+    /// bounds.
+    pub fn worst_case_interference_point(taskset: &[RTTask], i: usize) -> Option<(Time, Vec<usize>)> {
+        let point = Self::response_time(&taskset[i], |window| {
+            taskset[..i].iter()
+                .map(|higher| higher.interference(window))
+                .sum()
+        }).err()?;
+
+        let contributors = (0..i)
+            .filter(|&j| taskset[j].interference(point) > Time::zero())
+            .collect();
+
+        Some((point, contributors))
+    }
+
+    /// Worst-case end-to-end latency of a cause-effect chain, where each task
+    /// reads the data of the previous one at its release and writes its
+    /// results at completion (implicit communication). The tasks are given in
+    /// chain order, and are scheduled on a single processor under fixed
+    /// priorities, in the same order.
+    ///
+    /// Data entering the chain is processed within the response time of the
+    /// first task, while every following task may sample it up to one period
+    /// after it is produced, giving `R_1 + sum_{i >= 2} (T_i + R_i)`. Returns
+    /// `None` if any task misses its deadline.
+    pub fn end_to_end_latency(chain: &[RTTask]) -> Option<Time> {
+        let response_times = Self::response_time_fp(chain);
+
+        chain.iter().zip(response_times)
+            .enumerate()
+            .map(|(i, (task, response_time))| {
+                let sampling_delay = if i == 0 { Time::zero() } else { task.period };
+                Some(sampling_delay + response_time?)
+            })
+            .sum()
+    }
+
+    /// Audsley's Optimal Priority Assignment. Searches a fixed-priority
+    /// ordering for which every task meets its deadline according to
+    /// `response_time_fp`, assigning priorities from the lowest one upwards.
+    ///
+    /// Returns the indices of the tasks sorted by decreasing priority, or
+    /// `None` if no feasible assignment exists.
+    pub fn audsley_opa(taskset: &[RTTask]) -> Option<Vec<usize>> {
+        let mut unassigned: Vec<usize> = (0..taskset.len()).collect();
+        let mut ordering = Vec::with_capacity(taskset.len());
+
+        while !unassigned.is_empty() {
+            let lowest = unassigned.iter().rposition(|&candidate| {
+                Self::response_time(&taskset[candidate], |window| {
+                    unassigned.iter()
+                        .filter(|&&other| other != candidate)
+                        .map(|&other| taskset[other].interference(window))
+                        .sum()
+                }).is_ok()
+            })?;
+
+            ordering.push(unassigned.remove(lowest));
+        }
+
+        ordering.reverse();
+        Some(ordering)
+    }
+
+    /// Whether the taskset is schedulable under some fixed-priority
+    /// assignment. Deadline Monotonic is tried first, as it is optimal for
+    /// constrained deadlines, falling back to Audsley's algorithm otherwise.
+    pub fn is_feasible_fp(taskset: &[RTTask]) -> bool {
+        let deadline_monotonic = Self::deadline_monotonic(taskset);
+        if Self::response_time_fp(&deadline_monotonic).iter().all(Option::is_some) {
+            return true;
+        }
+
+        Self::audsley_opa(taskset).is_some()
+    }
+
+    /// Indices of the tasks with a job whose scheduling window, from its
+    /// release to its absolute deadline (excluded), contains the given time,
+    /// under synchronous periodic release from time zero. Whether the job has
+    /// already completed is not taken into account.
+    pub fn active_jobs_at(taskset: &[RTTask], t: Time) -> Vec<usize> {
+        if t < Time::zero() {
+            return Vec::new();
+        }
+
+        taskset.iter().enumerate()
+            .filter(|(_, task)| {
+                let last_release = task.period * f64::floor(t / task.period);
+                t - last_release < task.deadline
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Processor time left idle in [0, at) by the synchronous release of the
+    /// taskset, i.e. the slack available to aperiodic work executed at the
+    /// lowest priority level.
+    ///
+    /// The idle time of a work-conserving schedule does not depend on the
+    /// priority assignment, and equals the maximum of `s - W(s)` over the
+    /// release instants `s` in (0, at] and `at` itself, where `W(s)` is the
+    /// work released strictly before `s`.
+    pub fn available_slack(taskset: &[RTTask], at: Time) -> Time {
+        let released_before = |s: Time| -> Time {
+            taskset.iter()
+                .map(|task| task.interference(s))
+                .sum()
+        };
+
+        taskset.iter()
+            .flat_map(|task| (1..=f64::floor(at / task.period) as u64)
+                .map(|job| task.period * job as f64))
+            .chain(std::iter::once(at))
+            .map(|s| s - released_before(s))
+            .fold(Time::zero(), Time::max)
+    }
+
+    /// Fraction of jobs, per task, which miss their deadline when the taskset
+    /// is scheduled by EDF in the interval [0, horizon). Only jobs whose
+    /// deadline falls within the horizon are accounted for.
+    pub fn deadline_miss_ratio(taskset: &[RTTask], horizon: Time) -> Vec<f64> {
+        let result = crate::simulation::simulate(taskset, &EdfScheduler, horizon);
+
+        result.jobs.iter()
+            .zip(result.misses.iter())
+            .map(|(&jobs, &misses)| if jobs == 0 { 0.0 } else { misses as f64 / jobs as f64 })
+            .collect()
+    }
+
+    /// Fraction of the processor busy in each window of the given length,
+    /// when the taskset is scheduled by EDF over its hyperperiod. Each window
+    /// is reported with its start time, and the last one may be shorter.
+    ///
+    /// Panics if the resolution is not positive, or under the same conditions
+    /// as `hyperperiod`.
+    pub fn utilization_trace(taskset: &[RTTask], resolution: Time) -> Vec<(Time, f64)> {
+        assert!(resolution > Time::zero(), "Resolution must be positive");
+
+        let hyperperiod = Self::hyperperiod(taskset);
+        let windows = f64::ceil(hyperperiod / resolution) as usize;
+
+        let mut busy = vec![Time::zero(); windows];
+        for (_, start, end) in Timeline::record(taskset, &EdfScheduler, hyperperiod).executions() {
+            let first = f64::floor(start / resolution) as usize;
+            let last = usize::min(f64::ceil(end / resolution) as usize, windows);
+
+            for (window, busy) in busy.iter_mut().enumerate().take(last).skip(first) {
+                let window_start = resolution * window as f64;
+                let overlap = Time::min(end, window_start + resolution) - Time::max(start, window_start);
+                *busy = *busy + Time::max(overlap, Time::zero());
+            }
+        }
+
+        busy.into_iter().enumerate()
+            .map(|(window, busy)| {
+                let start = resolution * window as f64;
+                (start, busy / Time::min(resolution, hyperperiod - start))
+            })
+            .collect()
+    }
+
+    /// Quick Processor-demand Analysis (Zhang and Burns, 2009).
+    ///
+    /// Exact EDF test for sporadic tasks with arbitrary deadlines. Instead of
+    /// checking the dbf at every absolute deadline up to the hyperperiod, the
+    /// testing points are walked downwards from an upper bound on the first
+    /// deadline miss, skipping most of them. The taskset is rejected if that
+    /// bound cannot be computed, see `rbf_fixpoint`.
+    pub fn edf_qpa(taskset: &[RTTask]) -> bool {
+        Self::qpa(taskset).unwrap_or(false)
+    }
+
+    /// Like `edf_qpa`, but validates the taskset first, and returns
+    /// `RTError::IterationLimit` instead of rejecting the taskset when the
+    /// bound on the first deadline miss cannot be computed.
+    pub fn try_edf_qpa(taskset: &[RTTask]) -> Result<bool, RTError> {
+        Self::validate(taskset)?;
+
+        Self::qpa(taskset).ok_or(RTError::IterationLimit)
+    }
+
+    /// Exact EDF schedulability test on uniprocessors: the utilization bound
+    /// `U <= 1` for implicit-deadline tasksets, `edf_qpa` otherwise.
+    pub fn edf_schedulable(taskset: &[RTTask]) -> bool {
+        if Self::implicit_deadlines(taskset) {
+            Self::total_utilization(taskset) <= 1.0
+        } else {
+            Self::edf_qpa(taskset)
+        }
+    }
+
+    /// Exact EDF schedulability test which checks the processor demand at
+    /// every absolute deadline up to the end of the synchronous busy period.
+    /// It is as exact as `edf_schedulable`, but much slower, and is kept as a
+    /// reference. As `edf_qpa`, it rejects the taskset if that busy period
+    /// cannot be bounded.
+    pub fn edf_schedulable_naive(taskset: &[RTTask]) -> bool {
+        if Self::total_utilization(taskset) > 1.0 {
+            return false;
+        }
+
+        let Some(bound) = Self::edf_bound(taskset) else {
+            return false;
+        };
+
+        Self::testing_points(taskset, bound)
+            .into_iter()
+            .all(|t| Self::dbf(taskset, t) <= t)
+    }
+
+    /// Density bound for EDF, i.e. `sum(C / min(D, T)) <= 1`. Sufficient, but
+    /// not necessary, test for any deadline: it equals `total_density` for
+    /// constrained deadlines, and the utilization for the tasks whose deadline
+    /// is longer than the period, as their density alone would be unsound.
+    pub fn edf_density_test(taskset: &[RTTask]) -> bool {
+        compensated_sum(taskset.iter()
+            .map(|task| task.wcet / Time::min(task.deadline, task.period))) <= 1.0
+    }
+
+    /// Liu and Layland utilization bound for Rate Monotonic, i.e. `U <= n *
+    /// (2^(1/n) - 1)`. Sufficient test for implicit-deadline tasksets only,
+    /// rejects any other taskset.
+    pub fn rm_liu_layland(taskset: &[RTTask]) -> bool {
+        if !Self::implicit_deadlines(taskset) {
+            return false;
+        }
+
+        if taskset.is_empty() {
+            return true;
+        }
+
+        let n = taskset.len() as f64;
+        Self::total_utilization(taskset) <= n * (f64::powf(2.0, 1.0 / n) - 1.0)
+    }
+
+    /// Sr harmonic transformation test for Rate Monotonic (Han and Tyan,
+    /// 1997). Sufficient test for implicit-deadline tasksets only, rejects any
+    /// other taskset.
+    ///
+    /// Taking each period in turn as the base `T_b`, every period is reduced
+    /// to the largest `T_b * 2^k` not exceeding it. The transformed taskset is
+    /// harmonic, thus RM-schedulable as long as its utilization is at most
+    /// one, and it is harder to schedule than the original one, as its periods
+    /// are shorter. The test accepts if any of the transformed tasksets is
+    /// schedulable, so it is exact for harmonic tasksets and usually far less
+    /// pessimistic than `rm_liu_layland` for nearly harmonic ones.
+    pub fn sr_bound_test(taskset: &[RTTask]) -> bool {
+        if !Self::implicit_deadlines(taskset) {
+            return false;
+        }
+
+        if taskset.is_empty() {
+            return true;
+        }
+
+        taskset.iter().any(|base| {
+            let transformed = taskset.iter()
+                .map(|task| {
+                    let scale = f64::powi(2.0, f64::log2(task.period / base.period).floor() as i32);
+                    let period = if base.period * scale > task.period {
+                        base.period * scale / 2.0
+                    } else {
+                        base.period * scale
+                    };
+
+                    task.wcet / period
+                });
+
+            compensated_sum(transformed) <= 1.0
+        })
+    }
+
+    /// Whether the taskset, in any order, is schedulable under the given
+    /// policy. The fixed priority policies use the exact response-time
+    /// analysis, while EDF uses `edf_schedulable`, which is exact as well.
+    pub fn is_schedulable(taskset: &[RTTask], policy: SchedulingPolicy) -> bool {
+        let fixed_priority = |taskset: &[RTTask]| {
+            Self::response_time_fp(taskset).iter().all(Option::is_some)
+        };
+
+        match policy {
+            SchedulingPolicy::RateMonotonic => {
+                let mut rate_monotonic = taskset.to_vec();
+                rate_monotonic.sort_by_key(RTTask::period_key);
+                fixed_priority(&rate_monotonic)
+            },
+            SchedulingPolicy::DeadlineMonotonic =>
+                fixed_priority(&Self::deadline_monotonic(taskset)),
+            SchedulingPolicy::Edf => Self::edf_schedulable(taskset),
+            SchedulingPolicy::FixedPriorityOpa => Self::audsley_opa(taskset).is_some(),
+        }
+    }
+
+    /// Schedulability test registered with the given name, see `list_tests`.
+    pub fn test_by_name(name: &str) -> Option<SchedulabilityTest> {
+        SCHEDULABILITY_TESTS.iter()
+            .find(|(test_name, _)| *test_name == name)
+            .map(|&(_, test)| Box::new(test) as SchedulabilityTest)
+    }
+
+    /// Names of the schedulability tests available through `test_by_name`.
+    pub fn list_tests() -> Vec<&'static str> {
+        SCHEDULABILITY_TESTS.iter()
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Approximate EDF test (Fisher and Baruah, 2005).
+    ///
+    /// The dbf of each task is computed exactly for its first `ceil(1 /
+    /// epsilon)` jobs and then replaced by its linear upper bound `C + U * (t -
+    /// D)`, so only a polynomial number of testing points are checked. The test
+    /// is sufficient: it never accepts an unschedulable taskset, and it accepts
+    /// every taskset which is EDF-schedulable on a processor of speed `(1 -
+    /// epsilon)`.
+    ///
+    /// Panics if `epsilon` is not in (0, 1].
+    pub fn edf_approx(taskset: &[RTTask], epsilon: f64) -> bool {
+        assert!(epsilon > 0.0 && epsilon <= 1.0, "Epsilon must be in (0, 1]");
+
+        if Self::total_utilization(taskset) > 1.0 {
+            return false;
+        }
+
+        let exact_jobs = f64::ceil(1.0 / epsilon);
+        let approx_dbf = |t: Time| -> Time {
             taskset.iter()
-            .map(|task| task.period.as_nanos().floor() as i64)
-            .fold(1, |lcm, period| num::integer::lcm(lcm, period));
+                .map(|task| {
+                    if t < task.deadline {
+                        Time::zero()
+                    } else if t < task.deadline + task.period * (exact_jobs - 1.0) {
+                        task.wcet * (f64::floor((t - task.deadline) / task.period) + 1.0)
+                    } else {
+                        task.wcet + (t - task.deadline) * task.utilization()
+                    }
+                })
+                .sum()
+        };
+
+        taskset.iter()
+            .flat_map(|task| (0..exact_jobs as u64)
+                .map(|job| task.deadline + task.period * job as f64))
+            .all(|t| approx_dbf(t) <= t)
+    }
+
+    pub fn try_edf_approx(taskset: &[RTTask], epsilon: f64) -> Result<bool, RTError> {
+        if !(epsilon > 0.0 && epsilon <= 1.0) {
+            return Err(RTError::InvalidArgument(format!("epsilon {epsilon} not in (0, 1]")));
+        }
+
+        Self::validate(taskset)?;
+
+        Ok(Self::edf_approx(taskset, epsilon))
+    }
+
+    /// Grid of the verdicts of the given test on two-task tasksets, for
+    /// plotting the feasibility region in the utilization space.
+    ///
+    /// Both utilization ranges are sampled at `steps` evenly spaced values,
+    /// bounds included, and `region[a][b]` is the verdict for the a-th
+    /// utilization of the first task and the b-th of the second one. The
+    /// tasks have implicit deadlines, the first has a period of 1ms and the
+    /// second a period `period_ratio` times longer.
+    pub fn feasibility_region(
+        u1_range: std::ops::RangeInclusive<f64>,
+        u2_range: std::ops::RangeInclusive<f64>,
+        steps: usize,
+        period_ratio: f64,
+        test: impl Fn(&[RTTask]) -> bool,
+    ) -> Vec<Vec<bool>> {
+        let sample = |range: &std::ops::RangeInclusive<f64>, step: usize| {
+            if steps <= 1 {
+                *range.start()
+            } else {
+                range.start() + (range.end() - range.start()) * step as f64 / (steps - 1) as f64
+            }
+        };
+
+        let task = |utilization: f64, period: Time| RTTask {
+            wcet: period * utilization,
+            deadline: period,
+            period,
+        };
 
-        Time { value_ns: hyperperiod as f64 }
+        let period1 = Time::millis(1.0);
+        let period2 = period1 * period_ratio;
+
+        (0..steps)
+            .map(|a| (0..steps)
+                .map(|b| test(&[
+                    task(sample(&u1_range, a), period1),
+                    task(sample(&u2_range, b), period2),
+                ]))
+                .collect())
+            .collect()
+    }
+
+    /// Smallest factor, in [0, 1], by which all the deadlines can be uniformly
+    /// scaled while the taskset is still accepted by the given test. Returns
+    /// `None` if the test rejects the taskset with its original deadlines.
+    ///
+    /// The factor is found by bisection, within 1e-6, assuming the test is
+    /// sustainable with respect to deadlines, i.e. that a taskset accepted
+    /// with some deadlines is also accepted with longer ones.
+    pub fn min_deadline_scale(taskset: &[RTTask], test: impl Fn(&[RTTask]) -> bool) -> Option<f64> {
+        let accepts = |scale: f64| {
+            let scaled: Vec<RTTask> = taskset.iter()
+                .map(|task| RTTask {
+                    deadline: task.deadline * scale,
+                    ..task.clone()
+                })
+                .collect();
+
+            test(&scaled)
+        };
+
+        if !accepts(1.0) {
+            return None;
+        }
+
+        if accepts(0.0) {
+            return Some(0.0);
+        }
+
+        let (mut low, mut high) = (0.0, 1.0);
+        while high - low > 1e-6 {
+            let scale = f64::midpoint(low, high);
+            if accepts(scale) {
+                high = scale;
+            } else {
+                low = scale;
+            }
+        }
+
+        Some(high)
+    }
+
+    /// Largest factor, per task, by which its WCET can be multiplied, with the
+    /// other tasks unchanged, while the taskset is still accepted by the given
+    /// test. Factors below one mean that the WCET must shrink for the taskset
+    /// to be accepted, and zero that the test rejects it regardless.
+    ///
+    /// The factor is searched by bisection, within 1e-6 relative error, up to
+    /// `min(D, T) / C`, beyond which the task is infeasible by itself. Tasks
+    /// with zero WCET have infinite headroom. Like `min_deadline_scale`,
+    /// assumes the test is sustainable, here with respect to the WCETs.
+    pub fn per_task_wcet_headroom(taskset: &[RTTask], test: impl Fn(&[RTTask]) -> bool) -> Vec<f64> {
+        let mut scaled = taskset.to_vec();
+
+        (0..taskset.len())
+            .map(|i| {
+                let task = &taskset[i];
+                if task.wcet <= Time::zero() {
+                    return f64::INFINITY;
+                }
+
+                let mut accepts = |scale: f64| {
+                    scaled[i].wcet = task.wcet * scale;
+                    let accepted = test(&scaled);
+                    scaled[i].wcet = task.wcet;
+                    accepted
+                };
+
+                let limit = Time::min(task.deadline, task.period) / task.wcet;
+                if accepts(limit) {
+                    return limit;
+                }
+
+                if !accepts(0.0) {
+                    return 0.0;
+                }
+
+                let (mut low, mut high) = (0.0, limit);
+                while high - low > 1e-6 * limit {
+                    let scale = f64::midpoint(low, high);
+                    if accepts(scale) {
+                        low = scale;
+                    } else {
+                        high = scale;
+                    }
+                }
+
+                low
+            })
+            .collect()
+    }
+
+    /// Worst-case response time of the task under fixed priorities, with the
+    /// given higher priority tasks, as computed by `response_time_fp`.
+    fn fixed_priority_response_time(task: &RTTask, higher: &[RTTask]) -> Option<Time> {
+        let higher_utilization = Self::total_utilization(higher);
+        if higher_utilization >= 1.0 || higher_utilization + task.utilization() > 1.0 {
+            return None;
+        }
+
+        Self::response_time(task, |window| {
+            higher.iter()
+                .map(|higher| higher.interference(window))
+                .sum()
+        }).ok()
+    }
+
+    /// Worst-case response time of the task, given the interference caused by
+    /// higher priority tasks in a window of the given length. On failure,
+    /// returns the absolute deadline of the first job of the level-i busy
+    /// period which misses it, or of the job being analysed when the
+    /// iteration limit is reached.
+    fn response_time(task: &RTTask, interference: impl Fn(Time) -> Time) -> Result<Time, Time> {
+        let mut iterations = 0;
+        let mut worst = Time::zero();
+        for job in 0.. {
+            let job = job as f64;
+            let release = task.period * job;
+
+            let mut finish = task.wcet * (job + 1.0);
+            loop {
+                let next = task.wcet * (job + 1.0) + interference(finish);
+                iterations += 1;
+                if next - release > task.deadline || iterations > MAX_RESPONSE_TIME_ITERATIONS {
+                    return Err(release + task.deadline);
+                }
+
+                if next == finish {
+                    break;
+                }
+
+                finish = next;
+            }
+
+            worst = Time::max(worst, finish - release);
+            if finish <= release + task.period {
+                break;
+            }
+        }
+
+        Ok(worst)
     }
-}
\ No newline at end of file
+
+    /// QPA proper, returning `None` if the bound on the first deadline miss
+    /// is not found within the iteration limit.
+    fn qpa(taskset: &[RTTask]) -> Option<bool> {
+        if Self::total_utilization(taskset) > 1.0 {
+            return Some(false);
+        }
+
+        let Some(min_deadline) = taskset.iter().map(|task| task.deadline).min() else {
+            return Some(true);
+        };
+
+        let Some(mut t) = Self::last_deadline_before(taskset, Self::edf_bound(taskset)?) else {
+            return Some(true);
+        };
+
+        let mut demand = Self::dbf(taskset, t);
+        while demand <= t && demand > min_deadline {
+            if demand < t {
+                t = demand;
+            } else {
+                match Self::last_deadline_before(taskset, t) {
+                    Some(prev) => t = prev,
+                    None => return Some(true),
+                }
+            }
+
+            demand = Self::dbf(taskset, t);
+        }
+
+        Some(demand <= min_deadline)
+    }
+
+    /// Upper bound on the first deadline miss of an EDF schedule, i.e. the
+    /// minimum between the Zhang-Burns bound L_a and the synchronous busy
+    /// period L_b. Assumes the total utilization is at most one. Returns
+    /// `None` if the utilization is one and the busy period is not found
+    /// within the iteration limit, as L_a is unbounded.
+    fn edf_bound(taskset: &[RTTask]) -> Option<Time> {
+        let busy_period = Self::busy_period(taskset);
+
+        let utilization = Self::total_utilization(taskset);
+        if utilization >= 1.0 {
+            return busy_period;
+        }
+
+        let max_deadline = taskset.iter()
+            .map(|task| task.deadline)
+            .max()
+            .unwrap_or_default();
+
+        let la = taskset.iter()
+            .map(|task| (task.period - task.deadline) * task.utilization())
+            .sum::<Time>() / (1.0 - utilization);
+
+        let la = Time::max(max_deadline, la);
+        Some(busy_period.map_or(la, |busy_period| Time::min(busy_period, la)))
+    }
+
+    /// Largest absolute deadline `k * period + deadline` strictly before `t`.
+    fn last_deadline_before(taskset: &[RTTask], t: Time) -> Option<Time> {
+        taskset.iter()
+            .filter(|task| task.deadline < t)
+            .map(|task| {
+                let jobs = f64::ceil((t - task.deadline) / task.period) - 1.0;
+                task.deadline + task.period * jobs
+            })
+            .max()
+    }
+}
+
+/// Taskset analyzer for interactive tools, where tasks are added and removed
+/// one at a time. The total utilization and the hyperperiod are updated as
+/// the taskset changes, and the verdict of the test is cached until the
+/// next change.
+pub struct IncrementalAnalyzer {
+    tasks: Vec<RTTask>,
+    test: SchedulabilityTest,
+    utilization: CompensatedSum,
+    hyperperiod: Option<u64>,
+    verdict: std::cell::Cell<Option<bool>>,
+}
+
+impl IncrementalAnalyzer {
+    pub fn new(test: SchedulabilityTest) -> Self {
+        Self {
+            tasks: Vec::new(),
+            test,
+            utilization: CompensatedSum::default(),
+            hyperperiod: Some(1),
+            verdict: std::cell::Cell::new(None),
+        }
+    }
+
+    /// Append a task, with the lowest priority for fixed-priority tests.
+    pub fn add(&mut self, task: RTTask) {
+        self.utilization.add(task.utilization());
+        self.hyperperiod = self.hyperperiod
+            .zip(task.period.try_as_u64_nanos().filter(|&period| period > 0))
+            .and_then(|(hyperperiod, period)| checked_lcm(hyperperiod, period));
+
+        self.tasks.push(task);
+        self.verdict.set(None);
+    }
+
+    /// Remove the task at the given index. The hyperperiod is recomputed from
+    /// scratch, as it cannot be updated incrementally.
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> RTTask {
+        let task = self.tasks.remove(index);
+
+        self.utilization = CompensatedSum::default();
+        for task in &self.tasks {
+            self.utilization.add(task.utilization());
+        }
+        self.hyperperiod = self.tasks.iter()
+            .try_fold(1, |hyperperiod, task| {
+                let period = task.period.try_as_u64_nanos().filter(|&period| period > 0)?;
+                checked_lcm(hyperperiod, period)
+            });
+
+        self.verdict.set(None);
+        task
+    }
+
+    pub fn tasks(&self) -> &[RTTask] {
+        &self.tasks
+    }
+
+    pub fn total_utilization(&self) -> f64 {
+        self.utilization.value()
+    }
+
+    /// Hyperperiod of the current taskset. Returns `None` if the taskset is
+    /// empty, or under the same conditions as `RTUtils::try_hyperperiod`.
+    pub fn hyperperiod(&self) -> Option<Time> {
+        if self.tasks.is_empty() {
+            return None;
+        }
+
+        self.hyperperiod
+            .map(|hyperperiod| Time::nanos(hyperperiod as f64))
+    }
+
+    /// Verdict of the test on the current taskset.
+    pub fn is_schedulable(&self) -> bool {
+        match self.verdict.get() {
+            Some(verdict) => verdict,
+            None => {
+                let verdict = (self.test)(&self.tasks);
+                self.verdict.set(Some(verdict));
+                verdict
+            }
+        }
+    }
+}
+
+/// Min-priority queue of items keyed by `Time`, e.g. the events of a
+/// discrete-event simulation. Items with the same time are popped in
+/// insertion order.
+#[derive(Debug, Clone)]
+pub struct TimeQueue<T> {
+    heap: std::collections::BinaryHeap<QueueEntry<T>>,
+    pushed: u64,
+}
+
+#[derive(Debug, Clone)]
+struct QueueEntry<T> {
+    time: Time,
+    sequence: u64,
+    item: T,
+}
+
+impl<T> TimeQueue<T> {
+    pub fn new() -> Self {
+        Self {
+            heap: std::collections::BinaryHeap::new(),
+            pushed: 0,
+        }
+    }
+
+    pub fn push(&mut self, time: Time, item: T) {
+        self.heap.push(QueueEntry { time, sequence: self.pushed, item });
+        self.pushed += 1;
+    }
+
+    /// Remove the item with the earliest time.
+    pub fn pop(&mut self) -> Option<(Time, T)> {
+        self.heap.pop()
+            .map(|entry| (entry.time, entry.item))
+    }
+
+    /// Earliest time in the queue.
+    pub fn peek_time(&self) -> Option<Time> {
+        self.heap.peek()
+            .map(|entry| entry.time)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<T> Default for TimeQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PartialEq for QueueEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl<T> Eq for QueueEntry<T> { }
+
+impl<T> PartialOrd for QueueEntry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Reversed, so that the max-heap pops the earliest time, and then the
+/// earliest insertion.
+impl<T> Ord for QueueEntry<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (other.time, other.sequence).cmp(&(self.time, self.sequence))
+    }
+}
+
+/// `n` evenly spaced times from `start` to `end`, both included. Returns an
+/// empty vector if `n` is zero, and just `start` if `n` is one.
+pub fn time_linspace(start: Time, end: Time, n: usize) -> Vec<Time> {
+    match n {
+        0 => Vec::new(),
+        1 => vec![start],
+        n => (0..n)
+            .map(|i| if i == n - 1 { end } else { start + (end - start) * (i as f64 / (n - 1) as f64) })
+            .collect(),
+    }
+}
+
+/// Times from `start`, included, to `end`, excluded, spaced by `step`.
+/// Returns an empty vector if `step` is not positive, or if `start` or `end`
+/// is not finite. An infinite `step` yields only `start`.
+pub fn time_range(start: Time, end: Time, step: Time) -> Vec<Time> {
+    if step.value_ns.is_nan() || step <= Time::zero() ||
+        !start.value_ns.is_finite() || !end.value_ns.is_finite()
+    {
+        return Vec::new();
+    }
+
+    // `start + step * 0` would be NaN for an infinite step
+    (0..)
+        .map(|i| if i == 0 { start } else { start + step * i as f64 })
+        .take_while(|&time| time < end)
+        .collect()
+}
+
+/// Time read from the given environment variable, e.g. `HORIZON=500ms`, as
+/// parsed by `Time::from_str`. Returns `None` if the variable is not set, is
+/// not valid unicode, or cannot be parsed.
+pub fn parse_time_env(var: &str) -> Option<Time> {
+    std::env::var(var).ok()?
+        .parse().ok()
+}
+
+/// Total length covered by the union of the intervals, i.e. with their
+/// overlaps counted once. Empty intervals cover nothing.
+pub fn union_length(intervals: &[Interval]) -> Time {
+    let mut intervals: Vec<Interval> = intervals.iter()
+        .filter(|interval| interval.start < interval.end)
+        .copied()
+        .collect();
+    intervals.sort_by_key(|interval| interval.start);
+
+    let mut covered = Time::zero();
+    let mut current: Option<Interval> = None;
+    for interval in intervals {
+        match &mut current {
+            Some(merged) if interval.start <= merged.end => merged.end = Time::max(merged.end, interval.end),
+            _ => {
+                if let Some(merged) = current.replace(interval) {
+                    covered = covered + merged.length();
+                }
+            },
+        }
+    }
+
+    if let Some(merged) = current {
+        covered = covered + merged.length();
+    }
+
+    covered
+}
+
+/// Arithmetic mean of the times, or `None` if there are none.
+pub fn mean(times: &[Time]) -> Option<Time> {
+    if times.is_empty() {
+        return None;
+    }
+
+    Some(times.iter().copied().sum::<Time>() / times.len() as f64)
+}
+
+/// Bisection on a predicate which changes value once in [lo, hi], e.g. a
+/// monotone schedulability condition. Returns the earliest time, within `tol`,
+/// at which `f` has the same value it has at `hi`, or `lo` if `f` has the same
+/// value at both ends.
+///
+/// The search also stops when the interval cannot be split any further, so a
+/// `tol` below the floating point spacing at the magnitude of the bounds is
+/// effectively that spacing.
+///
+/// Panics if `tol` is not positive, or if `lo` or `hi` is not finite.
+pub fn bisect_time(lo: Time, hi: Time, tol: Time, f: impl Fn(Time) -> bool) -> Time {
+    assert!(tol > Time::zero(), "Tolerance must be positive");
+    assert!(lo.value_ns.is_finite() && hi.value_ns.is_finite(), "Bounds must be finite");
+
+    let target = f(hi);
+    if f(lo) == target {
+        return lo;
+    }
+
+    let (mut low, mut high) = (lo, hi);
+    while high - low > tol {
+        let mid = Time::midpoint(low, high);
+        if mid.value_ns == low.value_ns || mid.value_ns == high.value_ns {
+            break;
+        }
+
+        if f(mid) == target {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    high
+}
+
+/// Greatest common divisor of the two times, rounded down to integer
+/// nanoseconds. It is the other time if either is zero. Returns `None` if
+/// either time is not representable as an `u64` of nanoseconds.
+pub fn time_gcd(a: Time, b: Time) -> Option<Time> {
+    let gcd = num::integer::gcd(a.try_as_u64_nanos()?, b.try_as_u64_nanos()?);
+
+    Some(Time::nanos(gcd as f64))
+}
+
+/// Least common multiple of the two times, rounded down to integer
+/// nanoseconds. It is zero if either is zero. Returns `None` if either time is
+/// not representable as an `u64` of nanoseconds, or if the result overflows.
+pub fn time_lcm(a: Time, b: Time) -> Option<Time> {
+    checked_lcm(a.try_as_u64_nanos()?, b.try_as_u64_nanos()?)
+        .map(|lcm| Time::nanos(lcm as f64))
+}
+
+/// Least common multiple, or `None` on overflow.
+fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / num::integer::gcd(a, b)).checked_mul(b)
+}
+
+/// Kahan-Babuska-Neumaier compensated summation.
+pub(crate) fn compensated_sum(values: impl Iterator<Item = f64>) -> f64 {
+    let mut sum = CompensatedSum::default();
+    for value in values {
+        sum.add(value);
+    }
+
+    sum.value()
+}
+
+/// Running compensated sum, as computed by `compensated_sum`, for totals
+/// which are updated one value at a time.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    pub(crate) fn add(&mut self, value: f64) {
+        let next = self.sum + value;
+        if f64::abs(self.sum) >= f64::abs(value) {
+            self.compensation += (self.sum - next) + value;
+        } else {
+            self.compensation += (value - next) + self.sum;
+        }
+
+        self.sum = next;
+    }
+
+    pub(crate) fn value(&self) -> f64 {
+        if self.sum.is_finite() { self.sum + self.compensation } else { self.sum }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::simulate_until_miss;
+    use crate::test_utils::*;
+
+    fn ns(value: f64) -> Time {
+        Time::nanos(value)
+    }
+
+    /// Exact EDF verdict from the simulation of the synchronous release,
+    /// long enough to cover every deadline of the first hyperperiod. Only
+    /// valid for tasksets with utilization at most one, as an overload may
+    /// take many hyperperiods to cause a miss.
+    fn edf_simulation(taskset: &[RTTask]) -> bool {
+        let max_deadline = taskset.iter().map(|task| task.deadline).max().unwrap();
+        let horizon = RTUtils::hyperperiod(taskset) + max_deadline;
+
+        simulate_until_miss(taskset, &EdfScheduler, horizon).is_none()
+    }
+
+    // QPA -------------------------------------------------------------------
+
+    #[test]
+    fn dbf_counts_jobs_with_deadline_in_window() {
+        let taskset = [RTTask::new_ns(1, 3, 4), RTTask::new_ns(2, 5, 6)];
+
+        assert_eq!(RTUtils::dbf(&taskset, ns(2.0)), ns(0.0));
+        assert_eq!(RTUtils::dbf(&taskset, ns(3.0)), ns(1.0));
+        assert_eq!(RTUtils::dbf(&taskset, ns(5.0)), ns(3.0));
+        assert_eq!(RTUtils::dbf(&taskset, ns(7.0)), ns(4.0));
+        assert_eq!(RTUtils::dbf(&taskset, ns(11.0)), ns(7.0));
+    }
+
+    #[test]
+    fn busy_period_of_small_taskset() {
+        let taskset = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 6, 6)];
+        assert_eq!(RTUtils::busy_period(&taskset), Some(ns(3.0)));
+
+        let full = [RTTask::new_ns(2, 4, 4), RTTask::new_ns(3, 6, 6)];
+        assert_eq!(RTUtils::busy_period(&full), Some(ns(12.0)));
+
+        let overloaded = [RTTask::new_ns(3, 4, 4), RTTask::new_ns(3, 6, 6)];
+        assert_eq!(RTUtils::busy_period(&overloaded), None);
+    }
+
+    #[test]
+    fn qpa_on_known_tasksets() {
+        assert!(RTUtils::edf_qpa(&[]));
+        assert!(RTUtils::edf_qpa(&[RTTask::new_ns(2, 4, 4), RTTask::new_ns(3, 6, 6)]));
+        assert!(!RTUtils::edf_qpa(&[RTTask::new_ns(3, 4, 4), RTTask::new_ns(3, 6, 6)]));
+
+        // U = 0.5, but both jobs must complete by time 1
+        assert!(!RTUtils::edf_qpa(&[RTTask::new_ns(1, 1, 4), RTTask::new_ns(1, 1, 4)]));
+        // U = 1, with deadlines longer than the periods
+        assert!(RTUtils::edf_qpa(&[RTTask::new_ns(3, 8, 4), RTTask::new_ns(1, 4, 4)]));
+    }
+
+    #[test]
+    fn qpa_agrees_with_naive_dbf_test() {
+        for taskset in random_tasksets(102, 3000) {
+            assert_eq!(RTUtils::edf_qpa(&taskset), RTUtils::edf_schedulable_naive(&taskset),
+                "{taskset:?}");
+        }
+    }
+
+    #[test]
+    fn qpa_agrees_with_edf_simulation() {
+        for taskset in random_tasksets(1020, 3000) {
+            if RTUtils::total_utilization(&taskset) > 1.0 {
+                continue;
+            }
+
+            assert_eq!(RTUtils::edf_qpa(&taskset), edf_simulation(&taskset), "{taskset:?}");
+        }
+    }
+
+    // Simulation ------------------------------------------------------------
+
+    #[test]
+    fn deadline_miss_ratio_of_overloaded_taskset() {
+        let taskset = [RTTask::new_ns(2, 2, 2), RTTask::new_ns(1, 4, 4)];
+        assert_eq!(RTUtils::deadline_miss_ratio(&taskset, ns(8.0)), vec![0.0, 1.0]);
+
+        // the first job of the second task only runs in [2, 3) and misses,
+        // while the second one, released at 6, runs alone in [6, 8)
+        let taskset = [RTTask::new_ns(2, 2, 4), RTTask::new_ns(2, 3, 6)];
+        assert_eq!(RTUtils::deadline_miss_ratio(&taskset, ns(12.0)), vec![0.0, 0.5]);
+    }
+
+    // Harmonic merging ------------------------------------------------------
+
+    #[test]
+    fn merge_harmonic_folds_multiples() {
+        let taskset = [
+            RTTask::new_ns(1, 4, 4),
+            RTTask::new_ns(2, 8, 8),
+            RTTask::new_ns(3, 6, 6),
+            RTTask::new_ns(1, 5, 10),
+        ];
+
+        let merged = RTUtils::merge_harmonic(&taskset);
+        assert_eq!(merged, vec![
+            RTTask::new_ns(2, 4, 4),
+            RTTask::new_ns(3, 6, 6),
+            RTTask::new_ns(1, 5, 10),
+        ]);
+    }
+
+    #[test]
+    fn merge_harmonic_preserves_utilization_and_edf_verdict() {
+        for taskset in random_tasksets(109, 1000) {
+            let implicit: Vec<RTTask> = taskset.iter()
+                .map(|task| RTTask { deadline: task.period, ..task.clone() })
+                .collect();
+
+            let merged = RTUtils::merge_harmonic(&implicit);
+            assert!(merged.len() <= implicit.len());
+            assert!((RTUtils::total_utilization(&merged) - RTUtils::total_utilization(&implicit)).abs() < 1e-9);
+            assert_eq!(RTUtils::edf_qpa(&merged), RTUtils::edf_qpa(&implicit), "{implicit:?}");
+        }
+    }
+
+    // Approximate EDF -------------------------------------------------------
+
+    #[test]
+    fn edf_approx_is_sufficient() {
+        for taskset in random_tasksets(110, 2000) {
+            for epsilon in [1.0, 0.5, 0.2, 0.05] {
+                if RTUtils::edf_approx(&taskset, epsilon) {
+                    assert!(RTUtils::edf_qpa(&taskset), "{taskset:?}, epsilon {epsilon}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn edf_approx_accepts_tasksets_feasible_on_slower_processor() {
+        for taskset in random_tasksets(1100, 2000) {
+            for epsilon in [0.5, 0.2, 0.05] {
+                let slower: Vec<RTTask> = taskset.iter()
+                    .map(|task| task.inflate_wcet_factor(1.0 / (1.0 - epsilon)))
+                    .collect();
+
+                if RTUtils::edf_qpa(&slower) {
+                    assert!(RTUtils::edf_approx(&taskset, epsilon), "{taskset:?}, epsilon {epsilon}");
+                }
+            }
+        }
+    }
+
+    // Compensated summation -------------------------------------------------
+
+    #[test]
+    fn compensated_sum_does_not_drift() {
+        let naive: f64 = std::iter::repeat_n(0.1, 10).sum();
+        assert_ne!(naive, 1.0);
+        assert_eq!(compensated_sum(std::iter::repeat_n(0.1, 10)), 1.0);
+
+        let values = std::iter::once(1.0).chain(std::iter::repeat_n(1e-16, 10_000));
+        assert_eq!(values.clone().sum::<f64>(), 1.0);
+        assert!((compensated_sum(values) - (1.0 + 1e-12)).abs() < 1e-24);
+    }
+
+    #[test]
+    fn total_utilization_is_compensated() {
+        let taskset = vec![RTTask::new_ns(1, 10, 10); 10];
+        assert_eq!(RTUtils::total_utilization(&taskset), 1.0);
+
+        let times = vec![Time::nanos(0.1); 10];
+        assert_eq!(RTUtils::kahan_sum(&times).as_nanos(), 1.0);
+        assert_eq!(times.into_iter().sum::<Time>().as_nanos(), 1.0);
+    }
+
+    // Canonical order -------------------------------------------------------
+
+    #[test]
+    fn canonicalize_is_independent_of_input_order() {
+        let taskset = [
+            RTTask::new_ns(3, 12, 12),
+            RTTask::new_ns(1, 4, 4),
+            RTTask::new_ns(2, 6, 8),
+            RTTask::new_ns(1, 8, 8),
+            RTTask::new_ns(1, 4, 4),
+        ];
+
+        let canonical = RTUtils::canonicalize(&taskset);
+        assert!(RTUtils::is_taskset_sorted_by_period(&canonical));
+
+        let mut reversed = taskset.to_vec();
+        reversed.reverse();
+        assert_eq!(RTUtils::canonicalize(&reversed), canonical);
+
+        let mut rotated = taskset.to_vec();
+        rotated.rotate_left(2);
+        assert_eq!(RTUtils::canonicalize(&rotated), canonical);
+
+        assert_eq!(RTUtils::canonicalize(&canonical), canonical);
+    }
+
+    // Slack -----------------------------------------------------------------
+
+    #[test]
+    fn available_slack_at_several_points() {
+        // busy in [0, 3), [4, 5), [6, 9), idle in [3, 4), [5, 6), [9, 12)
+        let taskset = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 6, 6)];
+
+        for (at, slack) in [(0.0, 0.0), (3.0, 0.0), (4.0, 1.0), (5.0, 1.0), (6.0, 2.0),
+            (8.0, 2.0), (10.0, 3.0), (12.0, 5.0)]
+        {
+            assert_eq!(RTUtils::available_slack(&taskset, ns(at)), ns(slack), "at {at}");
+        }
+    }
+
+    #[test]
+    fn available_slack_matches_simulated_idle_time() {
+        for taskset in random_tasksets(113, 1000) {
+            if !RTUtils::edf_qpa(&taskset) {
+                continue;
+            }
+
+            for at in [1.0, 7.0, 24.0, 60.0] {
+                let busy = crate::simulation::simulate(&taskset, &EdfScheduler, ns(at)).busy_time;
+                assert_eq!(RTUtils::available_slack(&taskset, ns(at)), ns(at) - busy, "{taskset:?} at {at}");
+            }
+        }
+    }
+
+    #[test]
+    fn rbf_fixpoints() {
+        let taskset = [
+            RTTask::new_ns(1, 4, 4),
+            RTTask::new_ns(2, 6, 6),
+            RTTask::new_ns(3, 12, 12),
+        ];
+
+        assert_eq!(RTUtils::rbf(&taskset, 2, ns(6.0)), ns(7.0));
+        assert_eq!(RTUtils::rbf(&taskset, 2, ns(9.0)), ns(10.0));
+
+        // t: 6 -> 7 -> 9 -> 10 -> 10
+        assert_eq!(RTUtils::rbf_fixpoint(&taskset, 0), Some(ns(1.0)));
+        assert_eq!(RTUtils::rbf_fixpoint(&taskset, 1), Some(ns(3.0)));
+        assert_eq!(RTUtils::rbf_fixpoint(&taskset, 2), Some(ns(10.0)));
+
+        let full = [RTTask::new_ns(2, 4, 4), RTTask::new_ns(2, 4, 4)];
+        assert_eq!(RTUtils::rbf_fixpoint(&full, 1), Some(ns(4.0)));
+
+        let overloaded = [RTTask::new_ns(3, 4, 4), RTTask::new_ns(2, 4, 4)];
+        assert_eq!(RTUtils::rbf_fixpoint(&overloaded, 0), Some(ns(3.0)));
+        assert_eq!(RTUtils::rbf_fixpoint(&overloaded, 1), None);
+    }
+
+    #[test]
+    fn utilization_by_band_groups_by_period() {
+        let taskset = [
+            RTTask::new(Time::micros(100.0), Time::micros(500.0), Time::micros(500.0)),
+            RTTask::new(Time::millis(1.0), Time::millis(1.0), Time::millis(4.0)),
+            RTTask::new(Time::millis(1.0), Time::millis(5.0), Time::millis(5.0)),
+            RTTask::new(Time::millis(5.0), Time::millis(20.0), Time::millis(20.0)),
+            RTTask::new(Time::millis(10.0), Time::millis(10.0), Time::millis(10.0)),
+        ];
+        let bands = [Time::millis(1.0), Time::millis(10.0)];
+
+        let utilizations = RTUtils::utilization_by_band(&taskset, &bands);
+        let expected = [0.2, 0.45, 1.25];
+        assert_eq!(utilizations.len(), expected.len());
+        for (utilization, expected) in utilizations.iter().zip(expected) {
+            assert!((utilization - expected).abs() < 1e-12);
+        }
+
+        assert_eq!(RTUtils::utilization_by_band(&taskset, &[]).len(), 1);
+        assert_eq!(RTUtils::utilization_by_band(&[], &bands), vec![0.0; 3]);
+    }
+
+    #[test]
+    fn try_variants_reject_invalid_tasksets() {
+        let zero_period = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(1, 4, 0)];
+        let nan_wcet = [RTTask::new(Time::nanos(f64::NAN), ns(4.0), ns(4.0))];
+        let negative_deadline = [RTTask::new(ns(1.0), ns(-4.0), ns(4.0))];
+
+        assert_eq!(RTUtils::try_hyperperiod(&[]), Err(RTError::EmptyTaskset));
+        assert_eq!(RTUtils::try_hyperperiod(&zero_period), Err(RTError::InvalidTask(1)));
+        assert_eq!(RTUtils::try_total_utilization(&zero_period), Err(RTError::InvalidTask(1)));
+        assert_eq!(RTUtils::try_total_utilization(&nan_wcet), Err(RTError::InvalidTask(0)));
+        assert_eq!(RTUtils::try_edf_qpa(&negative_deadline), Err(RTError::InvalidTask(0)));
+        assert_eq!(RTUtils::try_total_utilization(&[]), Ok(0.0));
+
+        let fractional = [RTTask::new(ns(0.1), ns(0.5), ns(0.5))];
+        assert_eq!(RTUtils::try_hyperperiod(&fractional), Err(RTError::InvalidTask(0)));
+    }
+
+    #[test]
+    fn try_hyperperiod_reports_overflow() {
+        let taskset = [
+            RTTask::new_ns(1, 1 << 40, 1 << 40),
+            RTTask::new_ns(1, (1 << 40) - 1, (1 << 40) - 1),
+        ];
+
+        assert_eq!(RTUtils::try_hyperperiod(&taskset), Err(RTError::Overflow));
+        assert_eq!(RTUtils::try_hyperperiod(&taskset[..1]), Ok(ns((1u64 << 40) as f64)));
+    }
+
+    #[test]
+    fn try_edf_qpa_reports_the_iteration_limit() {
+        // Utilization exactly one with coprime periods: the busy period is
+        // the hyperperiod, far more than the iteration limit.
+        let taskset = [
+            RTTask::new_ns(999_983, 1_999_000, 1_999_966),
+            RTTask::new_ns(1_000_003, 2_000_000, 2_000_006),
+        ];
+
+        assert_eq!(RTUtils::try_edf_qpa(&taskset), Err(RTError::IterationLimit));
+        assert!(!RTUtils::edf_qpa(&taskset));
+        assert_eq!(RTUtils::try_edf_qpa(&[RTTask::new_ns(1, 2, 4)]), Ok(true));
+    }
+
+    #[test]
+    fn try_edf_approx_rejects_invalid_epsilon() {
+        let taskset = [RTTask::new_ns(1, 4, 4)];
+
+        assert!(matches!(RTUtils::try_edf_approx(&taskset, 0.0), Err(RTError::InvalidArgument(_))));
+        assert!(matches!(RTUtils::try_edf_approx(&taskset, 1.5), Err(RTError::InvalidArgument(_))));
+        assert!(matches!(RTUtils::try_edf_approx(&taskset, f64::NAN), Err(RTError::InvalidArgument(_))));
+        assert_eq!(RTUtils::try_edf_approx(&taskset, 0.1), Ok(true));
+    }
+
+    #[test]
+    fn registry_dispatches_each_name() {
+        let feasible = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 6, 6)];
+        let overloaded = [RTTask::new_ns(3, 4, 4), RTTask::new_ns(2, 4, 4)];
+
+        let names = RTUtils::list_tests();
+        assert_eq!(names.len(), SCHEDULABILITY_TESTS.len());
+        for name in names {
+            let test = RTUtils::test_by_name(name).unwrap();
+            assert!(test(&feasible), "{name} rejects a feasible taskset");
+            assert!(!test(&overloaded), "{name} accepts an overloaded taskset");
+        }
+
+        assert!(RTUtils::test_by_name("edf").is_some());
+        assert!(RTUtils::test_by_name("no-such-test").is_none());
+        assert!(RTUtils::test_by_name("rm-ll").unwrap()(&[]));
+    }
+
+    #[test]
+    fn dedup_removes_sub_tolerance_duplicates() {
+        let task = |wcet: f64, deadline: f64, period: f64|
+            RTTask::new(ns(wcet), ns(deadline), ns(period));
+
+        let tasks = vec![
+            task(1.0, 5.0, 10.4),
+            task(1.0, 9.0, 10.0),
+            task(1.0, 5.0, 10.6),
+            task(0.6, 5.0, 10.0),
+            task(1.3, 5.2, 9.8),
+            task(0.4, 5.0, 10.0),
+        ];
+
+        let mut deduped = tasks.clone();
+        RTUtils::dedup(&mut deduped);
+        assert_eq!(deduped, vec![
+            task(0.4, 5.0, 10.0),
+            task(1.0, 5.0, 10.0),
+            task(1.0, 9.0, 10.0),
+            task(1.0, 5.0, 11.0),
+        ]);
+
+        let set: std::collections::HashSet<_> = tasks.into_iter().collect();
+        assert_eq!(set.len(), deduped.len());
+        assert!(deduped.iter().all(|task| set.contains(task)));
+    }
+
+    #[test]
+    fn min_laxity_matches_the_tightest_task() {
+        let taskset = [
+            RTTask::new_ns(2, 10, 10),
+            RTTask::new_ns(5, 7, 12),
+            RTTask::new_ns(1, 4, 20),
+        ];
+
+        assert_eq!(RTUtils::laxity_vector(&taskset), vec![ns(8.0), ns(2.0), ns(3.0)]);
+        assert_eq!(RTUtils::min_laxity(&taskset), Some(taskset[1].laxity()));
+        assert_eq!(RTUtils::min_laxity(&[]), None);
+    }
+
+    #[test]
+    fn time_ranges_endpoints() {
+        assert_eq!(time_linspace(ns(0.0), ns(10.0), 5), vec![ns(0.0), ns(2.5), ns(5.0), ns(7.5), ns(10.0)]);
+        assert_eq!(time_linspace(ns(3.0), ns(1.0), 3), vec![ns(3.0), ns(2.0), ns(1.0)]);
+        assert_eq!(time_linspace(ns(3.0), ns(7.0), 1), vec![ns(3.0)]);
+        assert!(time_linspace(ns(3.0), ns(7.0), 0).is_empty());
+
+        let end = Time::millis(0.3);
+        let linspace = time_linspace(Time::zero(), end, 7);
+        assert_eq!(linspace.last().unwrap().value_ns, end.value_ns);
+
+        assert_eq!(time_range(ns(0.0), ns(10.0), ns(2.5)), vec![ns(0.0), ns(2.5), ns(5.0), ns(7.5)]);
+        assert_eq!(time_range(ns(1.0), ns(4.0), ns(1.0)), vec![ns(1.0), ns(2.0), ns(3.0)]);
+        assert!(time_range(ns(4.0), ns(4.0), ns(1.0)).is_empty());
+        assert!(time_range(ns(0.0), ns(4.0), ns(0.0)).is_empty());
+        assert!(time_range(ns(0.0), ns(4.0), ns(-1.0)).is_empty());
+        assert!(time_range(ns(0.0), ns(4.0), ns(f64::NAN)).is_empty());
+        assert!(time_range(ns(0.0), ns(f64::INFINITY), ns(1.0)).is_empty());
+        assert!(time_range(ns(f64::NEG_INFINITY), ns(4.0), ns(1.0)).is_empty());
+        assert_eq!(time_range(ns(1.0), ns(4.0), ns(f64::INFINITY)), vec![ns(1.0)]);
+        assert!(time_range(ns(4.0), ns(4.0), ns(f64::INFINITY)).is_empty());
+    }
+
+    // Fixed priority --------------------------------------------------------
+
+    /// Fixed priorities given by the task index, lower is higher.
+    struct IndexPriority;
+
+    impl crate::simulation::Scheduler for IndexPriority {
+        fn select(&self, _now: Time, _taskset: &[RTTask], jobs: &[crate::simulation::Job]) -> usize {
+            (0..jobs.len())
+                .min_by_key(|&j| (jobs[j].task, jobs[j].release))
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn only_opa_finds_a_feasible_ordering() {
+        let taskset = [RTTask::new_ns(52, 110, 100), RTTask::new_ns(52, 154, 140)];
+
+        // The second task misses its first deadline at 154 under DM/RM.
+        let deadline_monotonic = RTUtils::deadline_monotonic(&taskset);
+        assert_eq!(RTUtils::response_time_fp(&deadline_monotonic), vec![Some(ns(52.0)), None]);
+
+        assert_eq!(RTUtils::audsley_opa(&taskset), Some(vec![1, 0]));
+        assert_eq!(RTUtils::response_time_fp(&[taskset[1].clone(), taskset[0].clone()]),
+            vec![Some(ns(52.0)), Some(ns(108.0))]);
+        assert!(RTUtils::is_feasible_fp(&taskset));
+
+        assert!(!RTUtils::is_feasible_fp(&[RTTask::new_ns(3, 4, 4), RTTask::new_ns(2, 4, 4)]));
+    }
+
+    #[test]
+    fn deadline_monotonic_rta_matches_simulation() {
+        for taskset in random_tasksets(132, 3000) {
+            let taskset = RTUtils::deadline_monotonic(&RTUtils::enforce_constrained(&taskset));
+            let analysis = RTUtils::response_time_fp(&taskset).iter().all(Option::is_some);
+            let simulation = simulate_until_miss(&taskset, &IndexPriority, RTUtils::hyperperiod(&taskset))
+                .is_none();
+
+            assert_eq!(analysis, simulation, "{taskset:?}");
+        }
+    }
+
+    #[test]
+    fn switch_overhead_tightens_the_verdict() {
+        let taskset = [
+            RTTask::new_ns(1, 4, 4),
+            RTTask::new_ns(2, 6, 6),
+            RTTask::new_ns(3, 12, 12),
+        ];
+
+        assert_eq!(RTUtils::response_time_fp_with_overhead(&taskset, Time::zero()),
+            RTUtils::response_time_fp(&taskset));
+        assert_eq!(RTUtils::response_time_fp_with_overhead(&taskset, ns(0.25)),
+            vec![Some(ns(1.5)), Some(ns(4.0)), None]);
+        assert_eq!(RTUtils::response_time_fp_with_overhead(&taskset, ns(0.5)),
+            vec![Some(ns(2.0)), None, None]);
+
+        let mut previous = RTUtils::response_time_fp(&taskset);
+        for cost in [0.1, 0.2, 0.3, 0.5, 1.0, 2.0] {
+            let response_times = RTUtils::response_time_fp_with_overhead(&taskset, ns(cost));
+            for (previous, current) in previous.iter().zip(&response_times) {
+                match (previous, current) {
+                    (Some(previous), Some(current)) => assert!(current >= previous),
+                    (None, current) => assert!(current.is_none()),
+                    (Some(_), None) => {},
+                }
+            }
+
+            previous = response_times;
+        }
+        assert_eq!(previous, vec![None, None, None]);
+    }
+
+    #[test]
+    fn min_deadline_scale_finds_the_boundary() {
+        let rta = |taskset: &[RTTask]| RTUtils::response_time_fp(taskset).iter().all(Option::is_some);
+
+        // The deadline can shrink down to the WCET.
+        let scale = RTUtils::min_deadline_scale(&[RTTask::new_ns(2, 10, 10)], rta).unwrap();
+        assert!((0.2..0.2 + 1e-6).contains(&scale));
+
+        // The second task needs a deadline of 3, i.e. half its deadline.
+        let taskset = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 6, 6)];
+        let scale = RTUtils::min_deadline_scale(&taskset, rta).unwrap();
+        assert!((0.5..0.5 + 1e-6).contains(&scale));
+
+        assert_eq!(RTUtils::min_deadline_scale(&[RTTask::new_ns(0, 10, 10)], rta), Some(0.0));
+        assert_eq!(RTUtils::min_deadline_scale(&[RTTask::new_ns(3, 4, 4), RTTask::new_ns(2, 4, 4)], rta), None);
+    }
+
+    // Harmonic chains -------------------------------------------------------
+
+    #[test]
+    fn harmonic_chains_of_two_chains() {
+        let taskset: Vec<_> = [4, 9, 2, 3, 8, 27].into_iter()
+            .map(|period| RTTask::new_ns(1, period, period))
+            .collect();
+
+        assert_eq!(RTUtils::harmonic_chains(&taskset), vec![vec![2, 0, 4], vec![3, 1, 5]]);
+
+        let harmonic: Vec<_> = [10, 20, 40].into_iter()
+            .map(|period| RTTask::new_ns(1, period, period))
+            .collect();
+        assert_eq!(RTUtils::harmonic_chains(&harmonic), vec![vec![0, 1, 2]]);
+        assert!(RTUtils::harmonic_chains(&[]).is_empty());
+    }
+
+    #[test]
+    fn exact_utilization_at_one() {
+        use num::{BigRational, One};
+
+        // The utilization of the second task is lost when rounding the sum.
+        let taskset = [RTTask::new_ns(1, 1, 1), RTTask::new_ns(1, 1 << 60, 1 << 60)];
+        assert_eq!(RTUtils::total_utilization(&taskset), 1.0);
+        assert_eq!(RTUtils::total_utilization_exact(&taskset),
+            BigRational::one() + BigRational::new(1.into(), (1u64 << 60).into()));
+        assert!(RTUtils::total_utilization_exact(&taskset) > BigRational::one());
+
+        let thirds = [RTTask::new_ns(1, 3, 3), RTTask::new_ns(1, 3, 3), RTTask::new_ns(1, 3, 3)];
+        assert_eq!(RTUtils::total_utilization_exact(&thirds), BigRational::one());
+
+        let below = [RTTask::new_ns((1 << 53) - 1, 1 << 53, 1 << 53), RTTask::new_ns(0, 10, 10)];
+        assert!(RTUtils::total_utilization_exact(&below) < BigRational::one());
+    }
+
+    // Utilization bounds ----------------------------------------------------
+
+    fn implicit(taskset: &[RTTask]) -> Vec<RTTask> {
+        taskset.iter()
+            .map(|task| RTTask::new(task.wcet, task.period, task.period))
+            .collect()
+    }
+
+    #[test]
+    fn sr_bound_is_tighter_than_liu_layland() {
+        // Harmonic, at full utilization
+        let harmonic = [RTTask::new_ns(1, 2, 2), RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 8, 8)];
+        assert!(!RTUtils::rm_liu_layland(&harmonic));
+        assert!(RTUtils::sr_bound_test(&harmonic));
+
+        // Nearly harmonic: the period 9 is reduced to 8 with base 4
+        let nearly_harmonic = [RTTask::new_ns(2, 4, 4), RTTask::new_ns(3, 9, 9)];
+        assert!(!RTUtils::rm_liu_layland(&nearly_harmonic));
+        assert!(RTUtils::sr_bound_test(&nearly_harmonic));
+
+        assert!(!RTUtils::sr_bound_test(&[RTTask::new_ns(3, 4, 4), RTTask::new_ns(3, 8, 8)]));
+        assert!(!RTUtils::sr_bound_test(&[RTTask::new_ns(1, 3, 4)]));
+        assert!(RTUtils::sr_bound_test(&[]));
+    }
+
+    #[test]
+    fn sr_bound_is_sufficient() {
+        for taskset in random_tasksets(142, 3000) {
+            let mut taskset = implicit(&taskset);
+            taskset.sort_by_key(RTTask::period_key);
+
+            if RTUtils::rm_liu_layland(&taskset) {
+                assert!(RTUtils::sr_bound_test(&taskset), "{taskset:?}");
+            }
+
+            if RTUtils::sr_bound_test(&taskset) {
+                assert!(RTUtils::response_time_fp(&taskset).iter().all(Option::is_some), "{taskset:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn interference_point_of_an_infeasible_task() {
+        let opa_only = [RTTask::new_ns(52, 110, 100), RTTask::new_ns(52, 154, 140)];
+        assert_eq!(RTUtils::worst_case_interference_point(&opa_only, 1), Some((ns(154.0), vec![0])));
+        assert_eq!(RTUtils::worst_case_interference_point(&opa_only, 0), None);
+
+        // The zero-WCET task does not contribute to the interference.
+        let taskset = [
+            RTTask::new_ns(0, 3, 3),
+            RTTask::new_ns(2, 4, 4),
+            RTTask::new_ns(2, 6, 6),
+            RTTask::new_ns(2, 10, 12),
+        ];
+        assert_eq!(RTUtils::worst_case_interference_point(&taskset, 3), Some((ns(10.0), vec![1, 2])));
+        assert_eq!(RTUtils::worst_case_interference_point(&taskset, 2), None);
+    }
+
+    #[test]
+    fn per_task_headroom_is_asymmetric() {
+        let rta = |taskset: &[RTTask]| RTUtils::response_time_fp(taskset).iter().all(Option::is_some);
+        let taskset = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(1, 10, 10)];
+
+        // WCETs of 3.5 and 7 units respectively, others fixed.
+        let headroom = RTUtils::per_task_wcet_headroom(&taskset, rta);
+        assert_eq!(headroom.len(), 2);
+        assert!((headroom[0] - 3.5).abs() < 1e-5, "{headroom:?}");
+        assert!((headroom[1] - 7.0).abs() < 1e-5, "{headroom:?}");
+        assert!(headroom[0] <= 3.5 && headroom[1] <= 7.0);
+
+        let with_idle = [RTTask::new_ns(0, 4, 4), RTTask::new_ns(2, 10, 10)];
+        assert_eq!(RTUtils::per_task_wcet_headroom(&with_idle, rta), vec![f64::INFINITY, 5.0]);
+
+        let overloaded = [RTTask::new_ns(3, 4, 4), RTTask::new_ns(3, 4, 4), RTTask::new_ns(1, 8, 8)];
+        assert_eq!(RTUtils::per_task_wcet_headroom(&overloaded, rta)[2], 0.0);
+    }
+
+    #[test]
+    fn utilization_trace_shows_the_burst() {
+        // EDF runs the second task in [0, 1), the first in [1, 4), and the
+        // second again in [5, 6), then stays idle.
+        let taskset = [RTTask::new_ns(3, 10, 10), RTTask::new_ns(1, 5, 5)];
+
+        assert_eq!(RTUtils::utilization_trace(&taskset, ns(2.0)), vec![
+            (ns(0.0), 1.0),
+            (ns(2.0), 1.0),
+            (ns(4.0), 0.5),
+            (ns(6.0), 0.0),
+            (ns(8.0), 0.0),
+        ]);
+
+        let trace = RTUtils::utilization_trace(&taskset, ns(3.0));
+        assert_eq!(trace.iter().map(|&(start, _)| start).collect::<Vec<_>>(),
+            vec![ns(0.0), ns(3.0), ns(6.0), ns(9.0)]);
+        let load: Vec<_> = trace.iter().map(|&(_, load)| load).collect();
+        assert_eq!(load[0], 1.0);
+        assert!((load[1] - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(&load[2..], &[0.0, 0.0]);
+    }
+
+    #[test]
+    fn difficulty_metrics() {
+        let taskset = [
+            RTTask::new_ns(1, 5, 5),
+            RTTask::new_ns(6, 20, 20),
+            RTTask::new_ns(5, 100, 100),
+        ];
+
+        assert_eq!(RTUtils::period_ratio(&taskset), 20.0);
+        assert!((RTUtils::utilization_spread(&taskset) - (0.3 - 0.05)).abs() < 1e-12);
+
+        assert_eq!(RTUtils::period_ratio(&taskset[..1]), 1.0);
+        assert_eq!(RTUtils::utilization_spread(&taskset[..1]), 0.0);
+        assert_eq!(RTUtils::period_ratio(&[]), 1.0);
+        assert_eq!(RTUtils::utilization_spread(&[]), 0.0);
+    }
+
+    #[test]
+    fn feasibility_region_boundaries() {
+        const STEPS: usize = 21;
+        let utilization = |step: usize| step as f64 / (STEPS - 1) as f64;
+        let bound = 2.0 * (f64::sqrt(2.0) - 1.0);
+        let rta = |taskset: &[RTTask]| RTUtils::response_time_fp(taskset).iter().all(Option::is_some);
+
+        let liu_layland = RTUtils::feasibility_region(0.0..=1.0, 0.0..=1.0, STEPS, 1.5, RTUtils::rm_liu_layland);
+        let edf = RTUtils::feasibility_region(0.0..=1.0, 0.0..=1.0, STEPS, 1.5, RTUtils::edf_schedulable);
+        let exact = RTUtils::feasibility_region(0.0..=1.0, 0.0..=1.0, STEPS, 1.5, rta);
+        assert_eq!(liu_layland.len(), STEPS);
+        assert!(liu_layland.iter().all(|row| row.len() == STEPS));
+
+        for a in 0..STEPS {
+            for b in 0..STEPS {
+                let total = utilization(a) + utilization(b);
+                if (total - bound).abs() > 1e-9 {
+                    assert_eq!(liu_layland[a][b], total <= bound, "{a} {b}");
+                }
+
+                if (total - 1.0).abs() > 1e-9 {
+                    assert_eq!(edf[a][b], total <= 1.0, "{a} {b}");
+                }
+
+                assert!(!liu_layland[a][b] || exact[a][b], "{a} {b}");
+                assert!(!exact[a][b] || edf[a][b] || (total - 1.0).abs() <= 1e-9, "{a} {b}");
+            }
+        }
+
+        assert_eq!(RTUtils::feasibility_region(0.2..=0.9, 0.2..=0.9, 1, 2.0, RTUtils::rm_liu_layland),
+            vec![vec![true]]);
+    }
+
+    // Time helpers ----------------------------------------------------------
+
+    #[test]
+    fn mean_and_midpoint() {
+        assert_eq!(mean(&[ns(1.0), ns(2.0), ns(6.0)]), Some(ns(3.0)));
+        assert_eq!(mean(&[Time::millis(5.0)]), Some(Time::millis(5.0)));
+        assert_eq!(mean(&[]), None);
+
+        assert_eq!(ns(2.0).midpoint(ns(8.0)), ns(5.0));
+        assert_eq!(ns(-4.0).midpoint(ns(2.0)), ns(-1.0));
+
+        let huge = Time::nanos(f64::MAX);
+        assert_eq!(huge.midpoint(huge).value_ns, f64::MAX);
+        assert_eq!(huge.midpoint(-huge).value_ns, 0.0);
+    }
+
+    #[test]
+    fn active_jobs_at_several_times() {
+        let taskset = [RTTask::new_ns(1, 3, 5), RTTask::new_ns(2, 6, 6), RTTask::new_ns(1, 10, 4)];
+
+        assert_eq!(RTUtils::active_jobs_at(&taskset, ns(0.0)), vec![0, 1, 2]);
+        assert_eq!(RTUtils::active_jobs_at(&taskset, ns(2.9)), vec![0, 1, 2]);
+        // The deadline of the first job of the first task is excluded.
+        assert_eq!(RTUtils::active_jobs_at(&taskset, ns(3.0)), vec![1, 2]);
+        assert_eq!(RTUtils::active_jobs_at(&taskset, ns(5.0)), vec![0, 1, 2]);
+        assert_eq!(RTUtils::active_jobs_at(&taskset, ns(8.5)), vec![1, 2]);
+        assert_eq!(RTUtils::active_jobs_at(&taskset, ns(-1.0)), Vec::<usize>::new());
+        assert_eq!(RTUtils::active_jobs_at(&[], ns(1.0)), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn bisect_time_finds_the_flip() {
+        let tol = ns(0.01);
+
+        let flip = bisect_time(ns(0.0), ns(10.0), tol, |t| t.value_ns >= 7.3);
+        assert!(flip.value_ns >= 7.3 && flip.value_ns <= 7.3 + tol.value_ns, "{flip:?}");
+
+        let flip = bisect_time(ns(0.0), ns(10.0), tol, |t| t.value_ns < 4.0);
+        assert!(flip.value_ns >= 4.0 && flip.value_ns <= 4.0 + tol.value_ns, "{flip:?}");
+
+        assert_eq!(bisect_time(ns(2.0), ns(10.0), tol, |_| true), ns(2.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn bisect_time_rejects_zero_tolerance() {
+        bisect_time(ns(0.0), ns(10.0), Time::zero(), |t| t.value_ns >= 5.0);
+    }
+
+    #[test]
+    fn bisect_time_below_the_float_spacing() {
+        // The spacing at 1e18 is 128ns, so a 1ns tolerance cannot be reached.
+        let (lo, hi) = (ns(1e18), ns(1e18 + 4096.0));
+        let flip = bisect_time(lo, hi, ns(1.0), |t| t.value_ns >= 1e18 + 2048.0);
+        assert_eq!(flip.value_ns, 1e18 + 2048.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bounds must be finite")]
+    fn bisect_time_rejects_infinite_bounds() {
+        bisect_time(ns(0.0), ns(f64::INFINITY), ns(1.0), |t| t.value_ns >= 5.0);
+    }
+
+    #[test]
+    fn testing_points_are_the_absolute_deadlines() {
+        let taskset = [RTTask::new_ns(1, 3, 4), RTTask::new_ns(2, 5, 6)];
+        let points = RTUtils::testing_points(&taskset, ns(15.0));
+
+        // 3, 7, 11, 15 and 5, 11, with 11 shared
+        assert_eq!(points, vec![ns(3.0), ns(5.0), ns(7.0), ns(11.0), ns(15.0)]);
+
+        for t in 1..=15 {
+            let (t, before) = (ns(t as f64), ns(t as f64 - 0.5));
+            assert_eq!(RTUtils::dbf(&taskset, t) != RTUtils::dbf(&taskset, before), points.contains(&t));
+        }
+
+        assert!(RTUtils::testing_points(&taskset, ns(2.0)).is_empty());
+        assert_eq!(RTUtils::testing_points(&taskset, ns(3.0)), vec![ns(3.0)]);
+    }
+
+    #[test]
+    fn overloaded_prefix_terminates() {
+        // The higher priority tasks alone fully use the processor, and the
+        // deadline of the last task is long enough for the recurrence to run
+        // for about 1e12 iterations if not stopped.
+        let taskset = [
+            RTTask::new_ns(1, 2, 2),
+            RTTask::new_ns(1, 2, 2),
+            RTTask::new_ns(1, 1_000_000_000_000, 1_000_000_000_000),
+        ];
+        assert_eq!(RTUtils::response_time_fp(&taskset), vec![Some(ns(1.0)), Some(ns(2.0)), None]);
+
+        let overloaded = [RTTask::new_ns(3, 4, 4), RTTask::new_ns(2, 4, 4), RTTask::new_ns(1, 100, 100)];
+        assert_eq!(RTUtils::response_time_fp(&overloaded), vec![Some(ns(3.0)), None, None]);
+
+        // Utilization of exactly one with coprime periods: the level-1 busy
+        // period is the hyperperiod, beyond the iteration cap.
+        let full = [
+            RTTask::new_ns(999_983, 1_999_966, 1_999_966),
+            RTTask::new_ns(1_000_003, 2_000_006, 2_000_006),
+        ];
+        assert_eq!(RTUtils::rbf_fixpoint(&full, 1), None);
+        assert_eq!(RTUtils::busy_period(&full), None);
+    }
+
+    #[test]
+    fn time_queue_ordering_and_ties() {
+        let mut queue = TimeQueue::new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+
+        queue.push(ns(5.0), "c");
+        queue.push(ns(1.0), "a");
+        queue.push(ns(5.0), "d");
+        queue.push(ns(3.0), "b");
+        queue.push(ns(5.0), "e");
+        assert_eq!(queue.len(), 5);
+        assert_eq!(queue.peek_time(), Some(ns(1.0)));
+
+        let popped: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(popped, vec![
+            (ns(1.0), "a"),
+            (ns(3.0), "b"),
+            (ns(5.0), "c"),
+            (ns(5.0), "d"),
+            (ns(5.0), "e"),
+        ]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.peek_time(), None);
+
+        // Times closer than the `Time` tolerance are still ordered exactly.
+        queue.push(ns(2.3), "later");
+        queue.push(ns(2.1), "earlier");
+        assert_eq!(queue.pop().unwrap().1, "earlier");
+    }
+
+    #[test]
+    fn base_tick_of_a_clean_tickset() {
+        let taskset = [
+            RTTask::new(Time::millis(1.0), Time::millis(5.0), Time::millis(5.0)),
+            RTTask::new(Time::millis(2.0), Time::millis(12.5), Time::millis(12.5)),
+            RTTask::new(Time::millis(3.0), Time::millis(20.0), Time::millis(20.0)),
+        ];
+
+        assert_eq!(RTUtils::base_tick(&taskset), Time::millis(2.5));
+        assert_eq!(RTUtils::ticks_per_period(&taskset), vec![2, 5, 8]);
+
+        assert_eq!(RTUtils::base_tick(&[RTTask::new_ns(1, 7, 7), RTTask::new_ns(1, 9, 9)]), ns(1.0));
+        assert_eq!(RTUtils::base_tick(&[]), Time::zero());
+        assert!(RTUtils::ticks_per_period(&[]).is_empty());
+    }
+
+    #[test]
+    fn union_length_of_intervals() {
+        let interval = |start: f64, end: f64| Interval::new(ns(start), ns(end));
+
+        // Disjoint
+        assert_eq!(union_length(&[interval(0.0, 2.0), interval(5.0, 6.0)]), ns(3.0));
+        // Overlapping, nested and touching, unsorted
+        assert_eq!(union_length(&[
+            interval(4.0, 9.0),
+            interval(0.0, 3.0),
+            interval(5.0, 6.0),
+            interval(2.0, 4.0),
+            interval(12.0, 15.0),
+            interval(9.0, 10.0),
+        ]), ns(13.0));
+        // Empty and reversed intervals cover nothing
+        assert_eq!(union_length(&[interval(3.0, 3.0), interval(5.0, 1.0)]), Time::zero());
+        assert_eq!(union_length(&[]), Time::zero());
+    }
+
+    #[test]
+    fn rm_priorities_with_ties() {
+        let taskset = [
+            RTTask::new_ns(1, 20, 20),
+            RTTask::new_ns(1, 5, 5),
+            RTTask::new_ns(2, 10, 10),
+            RTTask::new_ns(1, 4, 5),
+            RTTask::new_ns(1, 8, 8),
+        ];
+
+        // Equal periods by index: task 1 before task 3.
+        assert_eq!(RTUtils::rm_priorities(&taskset), vec![4, 0, 3, 1, 2]);
+        assert!(RTUtils::rm_priorities(&[]).is_empty());
+    }
+
+    #[test]
+    fn enforce_constrained_clamps_the_deadlines() {
+        let taskset = [RTTask::new_ns(1, 12, 8), RTTask::new_ns(2, 5, 10), RTTask::new_ns(3, 6, 6)];
+        assert!(!RTUtils::constrained_deadlines(&taskset));
+
+        let constrained = RTUtils::enforce_constrained(&taskset);
+        assert!(RTUtils::constrained_deadlines(&constrained));
+        assert_eq!(constrained, vec![
+            RTTask::new_ns(1, 8, 8),
+            RTTask::new_ns(2, 5, 10),
+            RTTask::new_ns(3, 6, 6),
+        ]);
+    }
+
+    #[test]
+    fn incremental_analyzer_matches_batch() {
+        let mut rng = crate::generation::Rng::new(168);
+        let mut analyzer = IncrementalAnalyzer::new(Box::new(RTUtils::edf_qpa));
+        assert_eq!(analyzer.hyperperiod(), None);
+        assert!(analyzer.is_schedulable());
+
+        for step in 0..500 {
+            if analyzer.tasks().len() > 3 && step % 2 == 0 {
+                let index = uniform(&mut rng, 0, analyzer.tasks().len() as u64 - 1) as usize;
+                analyzer.remove(index);
+            } else {
+                analyzer.add(random_taskset(&mut rng, 1).remove(0));
+            }
+
+            let tasks = analyzer.tasks();
+            assert_eq!(analyzer.total_utilization(), RTUtils::total_utilization(tasks));
+            assert_eq!(analyzer.hyperperiod(), RTUtils::try_hyperperiod(tasks).ok());
+            assert_eq!(analyzer.is_schedulable(), RTUtils::edf_qpa(tasks));
+            // The cached verdict does not change.
+            assert_eq!(analyzer.is_schedulable(), RTUtils::edf_qpa(tasks));
+        }
+
+        // At the full utilization boundary
+        let mut analyzer = IncrementalAnalyzer::new(Box::new(RTUtils::edf_qpa));
+        for _ in 0..10 {
+            analyzer.add(RTTask::new_ns(1, 10, 10));
+        }
+        assert_eq!(analyzer.total_utilization(), 1.0);
+        assert!(analyzer.is_schedulable());
+    }
+
+    #[test]
+    fn density_test_is_only_sufficient() {
+        let taskset = [RTTask::new_ns(2, 3, 6), RTTask::new_ns(2, 4, 8)];
+        assert!(RTUtils::total_density(&taskset) > 1.0);
+        assert!(!RTUtils::edf_density_test(&taskset));
+        assert!(RTUtils::edf_qpa(&taskset));
+
+        for taskset in random_tasksets(170, 3000) {
+            if RTUtils::edf_density_test(&taskset) {
+                assert!(RTUtils::edf_qpa(&taskset), "{taskset:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn end_to_end_latency_of_a_three_task_chain() {
+        let chain = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 6, 6), RTTask::new_ns(3, 12, 12)];
+
+        // 1 + (6 + 3) + (12 + 10)
+        assert_eq!(RTUtils::end_to_end_latency(&chain), Some(ns(32.0)));
+        assert_eq!(RTUtils::end_to_end_latency(&chain[..1]), Some(ns(1.0)));
+
+        let infeasible = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 6, 6), RTTask::new_ns(3, 9, 12)];
+        assert_eq!(RTUtils::end_to_end_latency(&infeasible), None);
+    }
+
+    #[test]
+    fn core_utilizations_sum_to_the_total() {
+        let mut rng = crate::generation::Rng::new(177);
+        let taskset = random_taskset(&mut rng, 12);
+
+        // Round-robin partition on three cores
+        let mut partition = vec![Vec::new(); 3];
+        for (i, task) in taskset.iter().enumerate() {
+            partition[i % 3].push(task.clone());
+        }
+
+        let utilizations = RTUtils::core_utilizations(&partition);
+        assert_eq!(utilizations.len(), 3);
+        for (core, utilization) in partition.iter().zip(&utilizations) {
+            assert_eq!(*utilization, RTUtils::total_utilization(core));
+        }
+        assert!((utilizations.iter().sum::<f64>() - RTUtils::total_utilization(&taskset)).abs() < 1e-12);
+
+        assert_eq!(RTUtils::core_utilizations(&[Vec::new(), Vec::new()]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn weighted_utilization_generalizes_the_total() {
+        for taskset in random_tasksets(180, 200) {
+            let ones = vec![1.0; taskset.len()];
+            let twos = vec![2.0; taskset.len()];
+            let total = RTUtils::total_utilization(&taskset);
+
+            assert!((RTUtils::weighted_utilization(&taskset, &ones) - total).abs() < 1e-12);
+            assert!((RTUtils::weighted_utilization(&taskset, &twos) - 2.0 * total).abs() < 1e-12);
+        }
+
+        let taskset = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(1, 2, 2)];
+        assert_eq!(RTUtils::weighted_utilization(&taskset, &[0.0, 1.0]), 0.5);
+        assert_eq!(RTUtils::weighted_utilization(&taskset, &[4.0, 0.0]), 1.0);
+        assert_eq!(RTUtils::weighted_utilization(&[], &[]), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "There must be one weight per task")]
+    fn weighted_utilization_needs_a_weight_per_task() {
+        RTUtils::weighted_utilization(&[RTTask::new_ns(1, 4, 4)], &[1.0, 1.0]);
+    }
+
+    #[test]
+    fn time_from_the_environment() {
+        // SAFETY: the variables are unique to this test, and nothing else in
+        // the crate reads or writes the environment.
+        unsafe {
+            std::env::set_var("EVA_RT_COMMON_TEST_HORIZON", "500ms");
+            std::env::set_var("EVA_RT_COMMON_TEST_MALFORMED", "500 parsecs");
+        }
+
+        assert_eq!(parse_time_env("EVA_RT_COMMON_TEST_HORIZON"), Some(Time::millis(500.0)));
+        assert_eq!(parse_time_env("EVA_RT_COMMON_TEST_MALFORMED"), None);
+        assert_eq!(parse_time_env("EVA_RT_COMMON_TEST_UNSET"), None);
+    }
+
+    #[test]
+    fn response_times_by_rm_priority_match_the_positional_ones() {
+        for mut taskset in random_tasksets(184, 500) {
+            // So that the slice order is not the priority order
+            taskset.reverse();
+
+            let priorities = RTUtils::rm_priorities(&taskset);
+            let by_priority = RTUtils::response_time_fp_by_priority(&taskset, &priorities);
+
+            let mut sorted = taskset.clone();
+            sorted.sort_by_key(|task| task.period);
+            let positional = RTUtils::response_time_fp(&sorted);
+
+            assert_eq!(by_priority.len(), taskset.len());
+            for (rank, response) in positional.into_iter().enumerate() {
+                assert_eq!(by_priority[&(rank as u32)], response, "{taskset:?}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Priorities must be distinct")]
+    fn response_times_by_priority_need_distinct_priorities() {
+        RTUtils::response_time_fp_by_priority(&[RTTask::new_ns(1, 4, 4), RTTask::new_ns(1, 8, 8)], &[3, 3]);
+    }
+
+    #[test]
+    fn scale_periods_in_both_modes() {
+        let taskset = [RTTask::new_ns(1, 3, 4), RTTask::new_ns(2, 10, 10)];
+
+        assert_eq!(RTUtils::scale_periods(&taskset, 2.0, true), vec![
+            RTTask::new_ns(1, 8, 8),
+            RTTask::new_ns(2, 20, 20),
+        ]);
+        assert_eq!(RTUtils::scale_periods(&taskset, 2.0, false), vec![
+            RTTask::new_ns(1, 3, 8),
+            RTTask::new_ns(2, 10, 20),
+        ]);
+
+        // Shrinking the periods may leave arbitrary deadlines
+        let shrunk = RTUtils::scale_periods(&taskset, 0.5, false);
+        assert_eq!(shrunk, vec![RTTask::new_ns(1, 3, 2), RTTask::new_ns(2, 10, 5)]);
+        assert!(!RTUtils::constrained_deadlines(&shrunk));
+        assert_eq!(RTUtils::total_utilization(&shrunk), 2.0 * RTUtils::total_utilization(&taskset));
+    }
+
+    #[test]
+    fn utilization_product_plus_one_by_hand() {
+        // (1 + 1/4) * (1 + 1/2)
+        let taskset = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(1, 2, 2)];
+        assert_eq!(RTUtils::utilization_product_plus_one(&taskset), 1.875);
+
+        // (1 + 1/2) * (1 + 1/3), just within the hyperbolic bound
+        let taskset = [RTTask::new_ns(1, 2, 2), RTTask::new_ns(1, 3, 3)];
+        assert!((RTUtils::utilization_product_plus_one(&taskset) - 2.0).abs() < 1e-12);
+
+        assert_eq!(RTUtils::utilization_product_plus_one(&[RTTask::new_ns(0, 5, 5)]), 1.0);
+        assert_eq!(RTUtils::utilization_product_plus_one(&[]), 1.0);
+    }
+
+    #[test]
+    fn hyperbolic_bound_dominates_liu_layland() {
+        for taskset in random_tasksets(188, 2000) {
+            let taskset = implicit(&taskset);
+            if RTUtils::rm_liu_layland(&taskset) {
+                assert!(RTUtils::utilization_product_plus_one(&taskset) <= 2.0 + 1e-12, "{taskset:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn schedulability_by_policy() {
+        use SchedulingPolicy::*;
+
+        let verdicts = |taskset: &[RTTask]| [RateMonotonic, DeadlineMonotonic, Edf, FixedPriorityOpa]
+            .map(|policy| RTUtils::is_schedulable(taskset, policy));
+
+        // Only OPA finds a feasible priority order
+        let opa_only = [RTTask::new_ns(52, 110, 100), RTTask::new_ns(52, 154, 140)];
+        assert_eq!(verdicts(&opa_only), [false, false, true, true]);
+
+        // The shorter deadline has the longer period
+        let deadline_monotonic = [RTTask::new_ns(3, 3, 10), RTTask::new_ns(2, 5, 5)];
+        assert_eq!(verdicts(&deadline_monotonic), [false, true, true, true]);
+
+        // Full utilization, not harmonic
+        let edf_only = [RTTask::new_ns(2, 4, 4), RTTask::new_ns(3, 6, 6)];
+        assert_eq!(verdicts(&edf_only), [false, false, true, false]);
+
+        // In any order
+        assert_eq!(verdicts(&[RTTask::new_ns(3, 6, 6), RTTask::new_ns(1, 4, 4)]), [true; 4]);
+        assert_eq!(verdicts(&[RTTask::new_ns(2, 4, 4), RTTask::new_ns(3, 9, 6)]), [true; 4]);
+
+        assert_eq!(verdicts(&[RTTask::new_ns(3, 4, 4), RTTask::new_ns(3, 6, 6)]), [false; 4]);
+    }
+
+    #[test]
+    fn edf_tests_agree() {
+        let known = [
+            vec![RTTask::new_ns(2, 4, 4), RTTask::new_ns(3, 6, 6)],
+            vec![RTTask::new_ns(2, 3, 6), RTTask::new_ns(2, 4, 8)],
+            vec![RTTask::new_ns(3, 4, 4), RTTask::new_ns(3, 6, 6)],
+            vec![RTTask::new_ns(2, 2, 6), RTTask::new_ns(2, 3, 6)],
+        ];
+
+        // Both constrained and implicit deadlines, so that the utilization
+        // shortcut is checked too
+        let random = random_tasksets(194, 1000).into_iter()
+            .flat_map(|taskset| [implicit(&taskset), taskset]);
+
+        for taskset in known.into_iter().chain(random) {
+            let verdict = RTUtils::edf_schedulable(&taskset);
+            assert_eq!(verdict, RTUtils::edf_qpa(&taskset), "{taskset:?}");
+            assert_eq!(verdict, RTUtils::edf_schedulable_naive(&taskset), "{taskset:?}");
+        }
+    }
+
+    #[test]
+    fn preemptions_bound_of_a_small_taskset() {
+        // Response times 1, 3 and 10: the last task is preempted by at most
+        // ceil(10 / 4) + ceil(10 / 6) jobs
+        let taskset = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 6, 6), RTTask::new_ns(3, 12, 12)];
+        assert_eq!(RTUtils::max_preemptions_bound(&taskset), vec![Some(0), Some(1), Some(5)]);
+
+        let infeasible = [RTTask::new_ns(3, 4, 4), RTTask::new_ns(3, 6, 6)];
+        assert_eq!(RTUtils::max_preemptions_bound(&infeasible), vec![Some(0), None]);
+        assert!(RTUtils::max_preemptions_bound(&[]).is_empty());
+    }
+
+    #[test]
+    fn crpd_tightens_the_verdicts() {
+        let taskset = [RTTask::new_ns(1, 4, 4), RTTask::new_ns(2, 6, 6), RTTask::new_ns(3, 12, 12)];
+        let crpd = |delay: f64| [ns(delay), ns(delay), ns(delay)];
+
+        assert_eq!(RTUtils::response_time_fp_crpd(&taskset, &crpd(0.0)), RTUtils::response_time_fp(&taskset));
+
+        // 3 + 3 * 1.25 + 2 * 2.25
+        assert_eq!(RTUtils::response_time_fp_crpd(&taskset, &crpd(0.25)),
+            vec![Some(ns(1.0)), Some(ns(3.25)), Some(ns(11.25))]);
+        assert_eq!(RTUtils::response_time_fp_crpd(&taskset, &crpd(0.5)),
+            vec![Some(ns(1.0)), Some(ns(3.5)), None]);
+        assert_eq!(RTUtils::response_time_fp_crpd(&taskset, &crpd(1.0)),
+            vec![Some(ns(1.0)), Some(ns(4.0)), None]);
+
+        for taskset in random_tasksets(197, 500) {
+            let delays = vec![ns(0.5); taskset.len()];
+            let plain = RTUtils::response_time_fp(&taskset);
+            for (with_crpd, without) in RTUtils::response_time_fp_crpd(&taskset, &delays).into_iter().zip(plain) {
+                if let Some(with_crpd) = with_crpd {
+                    assert!(without.is_some_and(|without| without <= with_crpd), "{taskset:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shedding_keeps_the_most_valuable_tasks() {
+        let taskset = [
+            RTTask::new_ns(1, 2, 2),
+            RTTask::new_ns(1, 4, 4),
+            RTTask::new_ns(2, 5, 5),
+            RTTask::new_ns(1, 10, 10),
+        ];
+
+        // By utilization: 0.1 and 0.25 are shed, from 1.25 down to 0.9
+        let shed = RTUtils::shed_to_utilization(&taskset, 1.0, RTTask::utilization);
+        assert_eq!(shed, vec![taskset[0].clone(), taskset[2].clone()]);
+        assert_eq!(RTUtils::shed_to_utilization(&taskset, 0.5, RTTask::utilization), vec![taskset[0].clone()]);
+
+        // Equal values: the lower priority tasks go first
+        let shed = RTUtils::shed_to_utilization(&taskset, 1.0, |_| 1.0);
+        assert_eq!(shed, taskset[..2].to_vec());
+
+        assert_eq!(RTUtils::shed_to_utilization(&taskset, 2.0, RTTask::utilization), taskset.to_vec());
+        assert!(RTUtils::shed_to_utilization(&taskset, 0.0, RTTask::utilization).is_empty());
+
+        for taskset in random_tasksets(199, 500) {
+            let value = |task: &RTTask| task.period.value_ns;
+            let shed = RTUtils::shed_to_utilization(&taskset, 0.6, value);
+            assert!(RTUtils::total_utilization(&shed) <= 0.6, "{taskset:?}");
+
+            let removed = taskset.iter().filter(|task| !shed.contains(task));
+            let lowest_kept = shed.iter().map(value).fold(f64::INFINITY, f64::min);
+            assert!(removed.map(value).all(|removed| removed <= lowest_kept), "{taskset:?}");
+        }
+    }
+
+    #[test]
+    fn gcd_and_lcm_of_times() {
+        // Coprime
+        assert_eq!(time_gcd(ns(9.0), ns(14.0)), Some(ns(1.0)));
+        assert_eq!(time_lcm(ns(9.0), ns(14.0)), Some(ns(126.0)));
+
+        // Harmonic
+        assert_eq!(time_gcd(Time::millis(2.0), Time::millis(8.0)), Some(Time::millis(2.0)));
+        assert_eq!(time_lcm(Time::millis(2.0), Time::millis(8.0)), Some(Time::millis(8.0)));
+
+        assert_eq!(time_gcd(ns(12.0), ns(18.0)), Some(ns(6.0)));
+        assert_eq!(time_lcm(ns(12.0), ns(18.0)), Some(ns(36.0)));
+
+        // Rounded down to integer nanoseconds
+        assert_eq!(time_gcd(ns(12.7), ns(18.2)), Some(ns(6.0)));
+
+        assert_eq!(time_gcd(Time::zero(), ns(5.0)), Some(ns(5.0)));
+        assert_eq!(time_lcm(Time::zero(), ns(5.0)), Some(Time::zero()));
+
+        // Not representable, or overflowing
+        assert_eq!(time_gcd(ns(-1.0), ns(5.0)), None);
+        assert_eq!(time_lcm(ns(f64::INFINITY), ns(5.0)), None);
+        assert_eq!(time_lcm(ns(2f64.powi(40)), ns(2f64.powi(40) - 1.0)), None);
+    }
+}